@@ -0,0 +1,130 @@
+# Hauptmenü
+menu-title = GESCHWÄRZT
+button-play = Spielen
+button-tutorial = Tutorial
+button-continue = Fortsetzen
+button-new-campaign = Neue Kampagne
+button-endless = Endlos
+button-settings = Einstellungen
+button-quit = Beenden
+button-back = Zurück
+
+# Einstellungen
+settings-title = EINSTELLUNGEN
+settings-page-timers = Seitentimer: { $state }
+settings-language = Sprache: { $language }
+settings-free-look = Freie Sicht: { $state }
+settings-camera-speed = Kamerageschwindigkeit: { $speed }
+camera-speed-slow = Langsam
+camera-speed-normal = Normal
+camera-speed-fast = Schnell
+settings-readouts = Anzeigen: { $readouts }
+readouts-diegetic = Im Büro
+readouts-hud = Auf dem Bildschirm
+readouts-both = Beides
+settings-volume = Lautstärke { $bus }: { $volume } %
+bus-sfx = Effekte
+bus-ambience = Atmosphäre
+bus-music = Musik
+bus-ui = Oberfläche
+state-on = An
+state-off = Aus
+
+# Planer des Chefs
+planner-boss = Chef:
+planner-hint = Enter: Kappe, Leertaste: Seite oder Fenster, 1-4: Seite, Schreibtisch, Fenster, Chef, Mausrad oder Umschalt: Zoom, M: Lupe
+planner-page = Seite { $page }/{ $pages }
+planner-redacted = Geschwärzt: { $count }
+planner-unredacted = Übersehen: { $count }
+planner-score = Punkte: { $score }
+
+# Bildschirmanzeige
+hud-time = Zeit: { $time }
+hud-targets = Verbleibende Ziele: { $count }
+hud-ink = Tinte: { $ink } %
+hud-outrage = Wut: { $outrage } %
+
+# Tutorial
+tutorial-directive = Ausbildung: Folgen Sie den Anweisungen des Chefs
+tutorial-look = Drücken Sie die Leertaste, um auf die Seite auf Ihrem Schreibtisch zu schauen.
+tutorial-uncap = Ihr Marker ist verschlossen. Drücken Sie Enter, um die Kappe abzunehmen.
+tutorial-redact = Halten Sie die linke Maustaste gedrückt und ziehen Sie den Marker über den markierten Namen.
+tutorial-refill = Gut. Die Tinte geht zur Neige, also halten Sie den Marker ins Tintenfass, um ihn aufzufüllen.
+tutorial-turn = Drücken Sie den Marker jetzt auf den roten Knopf, um das Blatt abzulegen und das nächste zu nehmen.
+tutorial-done = So läuft das. Schwärzen Sie jeden Namen und jeden Ort auf dieser Seite und legen Sie sie ab. Verschließen Sie den Marker mit Enter, wenn Sie aufhören, ein offener Filz trocknet aus.
+
+# Anweisungen
+directive-bees = Schwärzen Sie alles über Bienen, SOFORT
+campaign-day = Tag { $day }: { $title }
+day1-title = Einarbeitung
+day1-directive = Willkommen. Schwärzen Sie Namen, Gesichter und Unterschriften aller Mitarbeiter
+day2-title = Haushaltszeit
+day2-directive = Schwärzen Sie jede Kontonummer und jeden Betrag über 10.000 Kronen
+day3-title = Der Vorfall
+day3-directive = Schwärzen Sie alle Ortsnamen und stempeln Sie den Bericht CLASSIFIED
+day4-title = Schadensbegrenzung
+day4-directive = Schwärzen Sie alles über Projekt Honeycomb und schreddern Sie, was es nicht geben dürfte
+day5-title = Das Leck
+day5-directive = Schwärzen Sie Namen und Gesicht des Informanten
+endless-names = Endlos: Schwärzen Sie jeden Namen
+endless-places = Endlos: Schwärzen Sie jeden Ort
+endless-codewords = Endlos: Schwärzen Sie jedes Codewort
+
+# Freischaltungen
+unlock-ink-bottle = Tintenfass
+unlock-chisel-tip = Keilspitzmarker
+unlock-desk-lamp = Schreibtischlampe
+
+# Endbildschirm
+end-leaked-title = VERTRAULICHE INFORMATIONEN DURCHGESICKERT
+end-leaked-subtitle = (ein Schicksal schlimmer als der Tod)
+end-complete-title = SCHICHT BEENDET
+end-complete-subtitle = (die Öffentlichkeit bleibt glücklich ahnungslos)
+end-redacted = Geschwärzte Zeichen: { $count }
+end-missed = Übersehene Zeichen: { $count }
+end-time-bonus = Zeitbonus: { $bonus }
+end-stamps = Stempel: { $correct } richtig, { $wrong } falsch, { $missed } vergessen
+end-shreds = Geschreddert: { $correct } richtig, { $wrong } falsch, { $missed } vergessen
+end-leaked-targets = Durchgesickerte Ziele: { $count }
+end-late-pages = Verspätet abgelegte Seiten: { $count }
+end-unlocked = Freigeschaltet: { $unlock }
+end-campaign-complete = Kampagne abgeschlossen. Das Ministerium dankt Ihnen.
+end-score = Punkte: { $score }
+end-new-high-score = Neuer Rekord! Platz { $rank }
+end-high-scores = Bestenliste:
+end-high-score-entry = { $rank }. { $score }
+button-replay = Nochmal
+button-next-day = Nächster Tag
+button-retry-day = Tag wiederholen
+button-main-menu = Hauptmenü
+
+# Dokumente im Endlosmodus, jede Lücke wird mit einem Namen, Ort, Codewort oder Nebenthema gefüllt
+procgen-memo = VERMERK // An: { $name } // Von: { $name } // Betreff: { $code }, Dokument { $page } ---
+procgen-email = @email Von: { $name }@ministry.gov // An: { $name }@ministry.gov // Betreff: { $place } ---
+procgen-note = @note Außenbericht, Dienststelle { $place }, Eintrag { $page }. //
+procgen-sentence-1 = { $name } wurde bis zur Überprüfung nach { $place } versetzt.
+procgen-sentence-2 = Die Operation { $code } wird wie mit { $name } besprochen fortgesetzt.
+procgen-sentence-3 = Erwähnen Sie { $code } niemandem außerhalb von { $place }.
+procgen-sentence-4 = Bitte sprechen Sie vor Freitag mit { $name } über { $filler }.
+procgen-sentence-5 = Die Lieferung aus { $place } kam beschädigt und unbeschriftet an.
+procgen-sentence-6 = { $name } und { $name } unterrichten den Ausschuss über { $code }.
+procgen-sentence-7 = Zur Erinnerung: Man hat { $filler } erneut verschoben.
+procgen-sentence-8 = Alle mit { $code } gekennzeichneten Akten sind bis Mittag an { $name } zurückzugeben.
+procgen-sentence-9 = Ein Zeuge in { $place } will { $name } an den Docks gesehen haben.
+procgen-sentence-10 = Bis auf Weiteres spricht niemand über { $filler }.
+procgen-sentence-11 = Der Haushalt für { $code } wurde an die Dienststelle { $place } übertragen.
+procgen-sentence-12 = { $name } meldet, man habe { $filler } genau nach Plan erledigt.
+procgen-sentence-13 = Dienstreisen nach { $place } sind für alle Mitarbeiter ausgesetzt.
+procgen-sentence-14 = Auf Nachfrage war { $name } nie in { $place }.
+procgen-sentence-15 = Das Protokoll der Sitzung zu { $code } ist verloren gegangen.
+procgen-sentence-16 = Vielen Dank für Ihre anhaltende Diskretion in Bezug auf { $filler }.
+procgen-filler-1 = den Hefter
+procgen-filler-2 = die Quartalsprüfung
+procgen-filler-3 = die Kantine
+procgen-filler-4 = den Ablagerückstand
+procgen-filler-5 = die Fensterreparatur
+procgen-filler-6 = die Dankbarkeitsveranstaltung
+procgen-filler-7 = die neue Tintenregelung
+procgen-filler-8 = die Aufzugsprüfung
+procgen-filler-9 = den Parkplatzplan
+procgen-filler-10 = die Brandschutzübung
@@ -0,0 +1,130 @@
+# Main menu
+menu-title = REDACTED
+button-play = Play
+button-tutorial = Tutorial
+button-continue = Continue
+button-new-campaign = New Campaign
+button-endless = Endless
+button-settings = Settings
+button-quit = Quit
+button-back = Back
+
+# Settings
+settings-title = SETTINGS
+settings-page-timers = Page Timers: { $state }
+settings-language = Language: { $language }
+settings-free-look = Free Look: { $state }
+settings-camera-speed = Camera Speed: { $speed }
+camera-speed-slow = Slow
+camera-speed-normal = Normal
+camera-speed-fast = Fast
+settings-readouts = Readouts: { $readouts }
+readouts-diegetic = In the office
+readouts-hud = On screen
+readouts-both = Both
+settings-volume = { $bus } Volume: { $volume }%
+bus-sfx = Effects
+bus-ambience = Ambience
+bus-music = Music
+bus-ui = Interface
+state-on = On
+state-off = Off
+
+# Boss planner
+planner-boss = Boss:
+planner-hint = Enter: cap, Space: page or window, 1-4: page, desk, window, boss, scroll or Shift: zoom, M: magnifier
+planner-page = Page { $page }/{ $pages }
+planner-redacted = Redacted: { $count }
+planner-unredacted = Unredacted: { $count }
+planner-score = Score: { $score }
+
+# On-screen readouts
+hud-time = Time: { $time }
+hud-targets = Targets left: { $count }
+hud-ink = Ink: { $ink }%
+hud-outrage = Outrage: { $outrage }%
+
+# Tutorial
+tutorial-directive = Training: follow the boss's instructions
+tutorial-look = Press Space to look down at the page on your desk.
+tutorial-uncap = Your marker is capped. Press Enter to take the cap off.
+tutorial-redact = Hold the left mouse button and drag the marker over the highlighted name.
+tutorial-refill = Good. Ink runs out, so hold the marker down in the ink well to top it up.
+tutorial-turn = Now press the marker on the red button to file the sheet and take the next one.
+tutorial-done = That's the job. Redact every name and place on this page and file it. Cap the marker with Enter when you stop, an open felt dries out.
+
+# Directives
+directive-bees = Redact anything related to Bees NOW
+campaign-day = Day { $day }: { $title }
+day1-title = Orientation
+day1-directive = Welcome aboard. Redact every employee name, face and signature
+day2-title = Budget Season
+day2-directive = Redact every account number and every amount over 10,000 crowns
+day3-title = The Incident
+day3-directive = Redact all place names and stamp the report CLASSIFIED
+day4-title = Damage Control
+day4-directive = Redact anything about Project Honeycomb and shred anything that should not exist
+day5-title = The Leak
+day5-directive = Redact the name and face of the leaker
+endless-names = Endless: Redact every name
+endless-places = Endless: Redact every place
+endless-codewords = Endless: Redact every codeword
+
+# Unlocks
+unlock-ink-bottle = Ink Bottle
+unlock-chisel-tip = Chisel Tip Marker
+unlock-desk-lamp = Desk Lamp
+
+# End screen
+end-leaked-title = CONFIDENTIAL INFORMATION LEAKED
+end-leaked-subtitle = (a fate worse than death)
+end-complete-title = SHIFT COMPLETE
+end-complete-subtitle = (the public remains blissfully uninformed)
+end-redacted = Characters Redacted: { $count }
+end-missed = Characters Missed: { $count }
+end-time-bonus = Time Bonus: { $bonus }
+end-stamps = Stamps: { $correct } correct, { $wrong } wrong, { $missed } missed
+end-shreds = Shredded: { $correct } correct, { $wrong } wrong, { $missed } missed
+end-leaked-targets = Targets Leaked: { $count }
+end-late-pages = Pages Filed Late: { $count }
+end-unlocked = Unlocked: { $unlock }
+end-campaign-complete = Campaign complete. The Ministry thanks you.
+end-score = Score: { $score }
+end-new-high-score = New high score! Rank #{ $rank }
+end-high-scores = High Scores:
+end-high-score-entry = { $rank }. { $score }
+button-replay = Replay
+button-next-day = Next Day
+button-retry-day = Retry Day
+button-main-menu = Main Menu
+
+# Endless documents, each slot is filled with a name, place, codeword or filler topic
+procgen-memo = MEMO // To: { $name } // From: { $name } // Re: { $code } document { $page } ---
+procgen-email = @email From: { $name }@ministry.gov // To: { $name }@ministry.gov // Subject: { $place } ---
+procgen-note = @note Field report, { $place } office, entry { $page }. //
+procgen-sentence-1 = { $name } has been reassigned to { $place } pending review.
+procgen-sentence-2 = Operation { $code } is to proceed as discussed with { $name }.
+procgen-sentence-3 = Do not mention { $code } to anyone outside { $place }.
+procgen-sentence-4 = Please see { $name } about { $filler } before Friday.
+procgen-sentence-5 = The shipment from { $place } arrived damaged and unlabelled.
+procgen-sentence-6 = { $name } and { $name } will brief the committee on { $code }.
+procgen-sentence-7 = Reminder that { $filler } has been postponed again.
+procgen-sentence-8 = All files marked { $code } must be returned to { $name } by noon.
+procgen-sentence-9 = A witness in { $place } claims to have seen { $name } at the docks.
+procgen-sentence-10 = Nobody is to discuss { $filler } until further notice.
+procgen-sentence-11 = The { $code } budget has been moved to the { $place } office.
+procgen-sentence-12 = { $name } reports that { $filler } went exactly as planned.
+procgen-sentence-13 = Travel to { $place } is suspended for all staff.
+procgen-sentence-14 = If asked, { $name } was never in { $place }.
+procgen-sentence-15 = The minutes from the { $code } meeting have been lost.
+procgen-sentence-16 = Thank you for your continued discretion regarding { $filler }.
+procgen-filler-1 = the stapler
+procgen-filler-2 = the quarterly audit
+procgen-filler-3 = the cafeteria
+procgen-filler-4 = the filing backlog
+procgen-filler-5 = the window repairs
+procgen-filler-6 = the appreciation event
+procgen-filler-7 = the new ink policy
+procgen-filler-8 = the lift inspection
+procgen-filler-9 = the parking rota
+procgen-filler-10 = the fire drill
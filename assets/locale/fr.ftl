@@ -0,0 +1,130 @@
+# Menu principal
+menu-title = CENSURÉ
+button-play = Jouer
+button-tutorial = Tutoriel
+button-continue = Continuer
+button-new-campaign = Nouvelle campagne
+button-endless = Sans fin
+button-settings = Options
+button-quit = Quitter
+button-back = Retour
+
+# Options
+settings-title = OPTIONS
+settings-page-timers = Minuteur par page : { $state }
+settings-language = Langue : { $language }
+settings-free-look = Regard libre : { $state }
+settings-camera-speed = Vitesse de la caméra : { $speed }
+camera-speed-slow = Lente
+camera-speed-normal = Normale
+camera-speed-fast = Rapide
+settings-readouts = Affichage : { $readouts }
+readouts-diegetic = Dans le bureau
+readouts-hud = À l'écran
+readouts-both = Les deux
+settings-volume = Volume { $bus } : { $volume } %
+bus-sfx = effets
+bus-ambience = ambiance
+bus-music = musique
+bus-ui = interface
+state-on = Oui
+state-off = Non
+
+# Planning du patron
+planner-boss = Patron :
+planner-hint = Entrée : capuchon, Espace : page ou fenêtre, 1-4 : page, bureau, fenêtre, patron, molette ou Maj : zoom, M : loupe
+planner-page = Page { $page }/{ $pages }
+planner-redacted = Censurés : { $count }
+planner-unredacted = Oubliés : { $count }
+planner-score = Score : { $score }
+
+# Affichage à l'écran
+hud-time = Temps : { $time }
+hud-targets = Cibles restantes : { $count }
+hud-ink = Encre : { $ink } %
+hud-outrage = Colère : { $outrage } %
+
+# Tutoriel
+tutorial-directive = Formation : suivez les instructions du patron
+tutorial-look = Appuyez sur Espace pour regarder la page sur votre bureau.
+tutorial-uncap = Votre marqueur est fermé. Appuyez sur Entrée pour retirer le capuchon.
+tutorial-redact = Maintenez le bouton gauche de la souris et passez le marqueur sur le nom surligné.
+tutorial-refill = Bien. L'encre s'épuise, alors maintenez le marqueur dans l'encrier pour le remplir.
+tutorial-turn = Appuyez maintenant le marqueur sur le bouton rouge pour classer la fiche et prendre la suivante.
+tutorial-done = Voilà le travail. Censurez chaque nom et chaque lieu de cette page puis classez-la. Rebouchez le marqueur avec Entrée quand vous arrêtez, un feutre ouvert sèche.
+
+# Directives
+directive-bees = Censurez tout ce qui concerne les abeilles, TOUT DE SUITE
+campaign-day = Jour { $day } : { $title }
+day1-title = Accueil
+day1-directive = Bienvenue. Censurez le nom, le visage et la signature de chaque employé
+day2-title = Saison budgétaire
+day2-directive = Censurez chaque numéro de compte et chaque montant supérieur à 10 000 couronnes
+day3-title = L'incident
+day3-directive = Censurez tous les noms de lieux et tamponnez le rapport CLASSIFIED
+day4-title = Limiter les dégâts
+day4-directive = Censurez tout ce qui concerne le projet Honeycomb et détruisez ce qui ne devrait pas exister
+day5-title = La fuite
+day5-directive = Censurez le nom et le visage de la taupe
+endless-names = Sans fin : censurez chaque nom
+endless-places = Sans fin : censurez chaque lieu
+endless-codewords = Sans fin : censurez chaque nom de code
+
+# Récompenses
+unlock-ink-bottle = Flacon d'encre
+unlock-chisel-tip = Marqueur biseauté
+unlock-desk-lamp = Lampe de bureau
+
+# Écran de fin
+end-leaked-title = FUITE D'INFORMATIONS CONFIDENTIELLES
+end-leaked-subtitle = (un sort pire que la mort)
+end-complete-title = SERVICE TERMINÉ
+end-complete-subtitle = (le public reste dans une ignorance bienheureuse)
+end-redacted = Caractères censurés : { $count }
+end-missed = Caractères oubliés : { $count }
+end-time-bonus = Bonus de temps : { $bonus }
+end-stamps = Tampons : { $correct } corrects, { $wrong } erronés, { $missed } oubliés
+end-shreds = Déchiquetés : { $correct } corrects, { $wrong } erronés, { $missed } oubliés
+end-leaked-targets = Cibles divulguées : { $count }
+end-late-pages = Pages classées en retard : { $count }
+end-unlocked = Débloqué : { $unlock }
+end-campaign-complete = Campagne terminée. Le Ministère vous remercie.
+end-score = Score : { $score }
+end-new-high-score = Nouveau record ! Rang n°{ $rank }
+end-high-scores = Meilleurs scores :
+end-high-score-entry = { $rank }. { $score }
+button-replay = Rejouer
+button-next-day = Jour suivant
+button-retry-day = Recommencer
+button-main-menu = Menu principal
+
+# Documents du mode sans fin, chaque emplacement reçoit un nom, un lieu, un nom de code ou un sujet anodin
+procgen-memo = NOTE // À : { $name } // De : { $name } // Objet : { $code }, document { $page } ---
+procgen-email = @email De : { $name }@ministry.gov // À : { $name }@ministry.gov // Objet : { $place } ---
+procgen-note = @note Rapport de terrain, bureau de { $place }, entrée { $page }. //
+procgen-sentence-1 = { $name } a été réaffecté à { $place } dans l'attente d'un examen.
+procgen-sentence-2 = L'opération { $code } doit se poursuivre comme convenu avec { $name }.
+procgen-sentence-3 = Ne parlez de { $code } à personne en dehors de { $place }.
+procgen-sentence-4 = Merci de voir { $name } au sujet de { $filler } avant vendredi.
+procgen-sentence-5 = La livraison venue de { $place } est arrivée abîmée et sans étiquette.
+procgen-sentence-6 = { $name } et { $name } informeront la commission au sujet de { $code }.
+procgen-sentence-7 = Rappel : on a encore reporté { $filler }.
+procgen-sentence-8 = Tous les dossiers marqués { $code } doivent être rendus à { $name } avant midi.
+procgen-sentence-9 = Un témoin à { $place } affirme avoir vu { $name } sur les docks.
+procgen-sentence-10 = Personne ne doit évoquer { $filler } jusqu'à nouvel ordre.
+procgen-sentence-11 = Le budget { $code } a été transféré au bureau de { $place }.
+procgen-sentence-12 = { $name } signale que tout s'est passé comme prévu pour { $filler }.
+procgen-sentence-13 = Les déplacements à { $place } sont suspendus pour tout le personnel.
+procgen-sentence-14 = Si on vous le demande, { $name } n'a jamais mis les pieds à { $place }.
+procgen-sentence-15 = Le compte rendu de la réunion { $code } a été perdu.
+procgen-sentence-16 = Merci de votre discrétion constante concernant { $filler }.
+procgen-filler-1 = l'agrafeuse
+procgen-filler-2 = l'audit trimestriel
+procgen-filler-3 = la cantine
+procgen-filler-4 = la pile de dossiers en retard
+procgen-filler-5 = la réparation des fenêtres
+procgen-filler-6 = l'événement de gratitude
+procgen-filler-7 = la nouvelle politique d'encre
+procgen-filler-8 = l'inspection de l'ascenseur
+procgen-filler-9 = la rotation des places de parking
+procgen-filler-10 = l'exercice d'évacuation
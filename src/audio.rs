@@ -1,41 +1,208 @@
-use bevy::{audio::PlaybackMode, platform::collections::HashMap, prelude::*};
+use bevy::{
+    audio::{AudioSinkPlayback, SpatialScale, Volume},
+    ecs::system::EntityCommands,
+    platform::collections::HashMap,
+    prelude::*,
+};
 
-use crate::GameState;
+use crate::{GameState, settings::Settings};
+
+// how far the ducked buses drop while the boss is speaking
+const DUCK_LEVEL: f32 = 0.3;
+// how quickly the ducked buses dip and recover, in volume per second
+const DUCK_SPEED: f32 = 3.0;
+// rodio's falloff starts a unit away, so shrink the room until the desk is within reach and
+// the window is a little past it
+const SPATIAL_SCALE: f32 = 0.15;
+// distance between the listener's ears, in world units before scaling
+pub const EAR_GAP: f32 = 0.3;
 
 #[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
 pub enum Sounds {
     VineBoom,
     MarkerDrag,
+    MarkerDry,
+    MarkerCap,
     Slurp,
     Correct,
     Wrong,
     GlassCrack,
     GlassShatter,
     Mob,
+    // music stems, all the same length so they loop in step
+    MusicDrone,
+    MusicPulse,
+    MusicTicks,
+    MusicStrings,
+    StingPage,
+    StingBreach,
+}
+
+// Mixer bus a sound plays through, each with its own volume in the settings
+#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
+pub enum Bus {
+    Sfx,
+    Ambience,
+    Music,
+    Ui,
+}
+
+impl Bus {
+    pub const ALL: [Bus; 4] = [Bus::Sfx, Bus::Ambience, Bus::Music, Bus::Ui];
+
+    // key used for the bus in the settings file
+    pub fn code(&self) -> &'static str {
+        match self {
+            Bus::Sfx => "sfx",
+            Bus::Ambience => "ambience",
+            Bus::Music => "music",
+            Bus::Ui => "ui",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|bus| bus.code() == code)
+    }
+
+    // locale key of the bus's name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Bus::Sfx => "bus-sfx",
+            Bus::Ambience => "bus-ambience",
+            Bus::Music => "bus-music",
+            Bus::Ui => "bus-ui",
+        }
+    }
+
+    fn ducked(&self) -> bool {
+        *self == Bus::Ambience
+    }
+}
+
+// Where a sound comes from, relative to the camera's listener
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Emitter {
+    // the same in both ears wherever the camera looks
+    Flat,
+    At(Vec3),
+    // moves with an entity, like the marker
+    Follow(Entity),
 }
 
+// Plays a sound once, or however `setting` says
 #[derive(Event)]
 pub struct SoundEvent {
     pub sound: Sounds,
+    pub bus: Bus,
+    pub emitter: Emitter,
     pub setting: PlaybackSettings,
 }
 
+// Refers to one playing loop, so it can be faded or stopped without touching the others
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct LoopHandle(Entity);
+
+// What a loop plays, where from and how loud, for loops that replace one another
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct LoopSpec {
+    pub sound: Sounds,
+    pub bus: Bus,
+    pub emitter: Emitter,
+    pub volume: f32,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum LoopCommand {
+    // eases to `volume` over `seconds`
+    Fade { volume: f32, seconds: f32 },
+    // playback speed, which also shifts the pitch
+    Speed(f32),
+    // fades out over `seconds` and then stops for good
+    Stop { seconds: f32 },
+}
+
+#[derive(Event)]
+pub struct LoopEvent {
+    pub handle: LoopHandle,
+    pub command: LoopCommand,
+}
+
+// Dips the ambience for `seconds`, so the boss can be heard over the mob
 #[derive(Event)]
-pub struct StopLoopEvent;
+pub struct DuckEvent {
+    pub seconds: f32,
+}
 
 #[derive(Resource, Default)]
 pub struct SoundBank {
     pub sounds: HashMap<Sounds, Handle<AudioSource>>,
-    pub looping: bool,
 }
 
+// Ducking state shared by every bus
+#[derive(Resource)]
+struct Mixer {
+    // 1.0 when nothing is ducked
+    duck_level: f32,
+    duck_timer: Timer,
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self {
+            duck_level: 1.0,
+            duck_timer: Timer::default(),
+        }
+    }
+}
+
+impl Mixer {
+    fn gain(&self, bus: Bus, settings: &Settings) -> f32 {
+        let duck = if bus.ducked() { self.duck_level } else { 1.0 };
+        settings.volume(bus) * duck
+    }
+}
+
+// A sound playing through the mixer, volume is before the bus is applied
 #[derive(Component)]
-struct SoundComponent;
+struct Channel {
+    bus: Bus,
+    volume: f32,
+    target: f32,
+    // volume per second, zero jumps straight to the target
+    rate: f32,
+    speed: f32,
+    stopping: bool,
+}
+
+impl Channel {
+    fn new(bus: Bus, volume: f32) -> Self {
+        Self {
+            bus,
+            volume,
+            target: volume,
+            rate: 0.0,
+            speed: 1.0,
+            stopping: false,
+        }
+    }
+
+    fn fade_to(&mut self, volume: f32, seconds: f32) {
+        self.target = volume;
+        self.rate = if seconds > 0.0 {
+            (volume - self.volume).abs() / seconds
+        } else {
+            0.0
+        };
+    }
+}
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Startup, setup)
+    app.init_resource::<Mixer>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, mix)
         .add_observer(play_sound)
-        .add_observer(stop_loop);
+        .add_observer(control_loop)
+        .add_observer(duck);
 }
 
 // loads all sound effects and inserts resource
@@ -49,6 +216,12 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     sound_bank
         .sounds
         .insert(Sounds::MarkerDrag, asset_server.load("audio/marker.ogg"));
+    sound_bank
+        .sounds
+        .insert(Sounds::MarkerDry, asset_server.load("audio/marker-dry.wav"));
+    sound_bank
+        .sounds
+        .insert(Sounds::MarkerCap, asset_server.load("audio/marker-cap.wav"));
     sound_bank
         .sounds
         .insert(Sounds::Slurp, asset_server.load("audio/slurp2.ogg"));
@@ -69,41 +242,184 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     sound_bank
         .sounds
         .insert(Sounds::Mob, asset_server.load("audio/mob.ogg"));
+    for (sound, path) in [
+        (Sounds::MusicDrone, "audio/music/drone.wav"),
+        (Sounds::MusicPulse, "audio/music/pulse.wav"),
+        (Sounds::MusicTicks, "audio/music/ticks.wav"),
+        (Sounds::MusicStrings, "audio/music/strings.wav"),
+        (Sounds::StingPage, "audio/music/sting-page.wav"),
+        (Sounds::StingBreach, "audio/music/sting-breach.wav"),
+    ] {
+        sound_bank.sounds.insert(sound, asset_server.load(path));
+    }
 
     commands.insert_resource(sound_bank);
 }
 
-fn play_sound(event: On<SoundEvent>, mut commands: Commands, mut sound_bank: ResMut<SoundBank>) {
+// starts `sound` looping on `bus`, fading in to `volume` over `fade_in` seconds
+pub fn start_loop(
+    commands: &mut Commands,
+    sound_bank: &SoundBank,
+    sound: Sounds,
+    bus: Bus,
+    emitter: Emitter,
+    volume: f32,
+    fade_in: f32,
+) -> LoopHandle {
+    let mut channel = Channel::new(bus, 0.0);
+    channel.fade_to(volume, fade_in);
+    let mut entity = commands.spawn((
+        channel,
+        // the mixer sets the real volume once the sink exists
+        place(PlaybackSettings::LOOP.with_volume(Volume::SILENT), emitter),
+        DespawnOnExit(GameState::PLAYING),
+    ));
+    if let Some(handle) = sound_bank.sounds.get(&sound) {
+        entity.insert(AudioPlayer::new(handle.clone()));
+    }
+    attach(&mut entity, emitter);
+    LoopHandle(entity.id())
+}
+
+// fades `from` out while a new loop fades in over the same time
+pub fn crossfade(
+    commands: &mut Commands,
+    sound_bank: &SoundBank,
+    from: LoopHandle,
+    to: LoopSpec,
+    seconds: f32,
+) -> LoopHandle {
+    commands.trigger(LoopEvent {
+        handle: from,
+        command: LoopCommand::Stop { seconds },
+    });
+    start_loop(
+        commands, sound_bank, to.sound, to.bus, to.emitter, to.volume, seconds,
+    )
+}
+
+// spatial sounds are panned and attenuated from wherever their entity is
+fn place(setting: PlaybackSettings, emitter: Emitter) -> PlaybackSettings {
+    if emitter == Emitter::Flat {
+        return setting;
+    }
+    PlaybackSettings {
+        spatial: true,
+        spatial_scale: Some(SpatialScale::new(SPATIAL_SCALE)),
+        ..setting
+    }
+}
+
+fn attach(entity: &mut EntityCommands, emitter: Emitter) {
+    match emitter {
+        Emitter::Flat => {}
+        Emitter::At(position) => {
+            entity.insert(Transform::from_translation(position));
+        }
+        Emitter::Follow(parent) => {
+            entity.insert((Transform::default(), ChildOf(parent)));
+        }
+    }
+}
+
+fn play_sound(
+    event: On<SoundEvent>,
+    mut commands: Commands,
+    sound_bank: Res<SoundBank>,
+    mixer: Res<Mixer>,
+    settings: Res<Settings>,
+) {
     if let Some(handle) = sound_bank.sounds.get(&event.sound) {
-        commands.spawn((
+        let volume = event.setting.volume.to_linear();
+        let gain = mixer.gain(event.bus, &settings);
+        let mut entity = commands.spawn((
             AudioPlayer::new(handle.clone()),
-            event.setting,
-            SoundComponent,
+            place(
+                event.setting.with_volume(Volume::Linear(volume * gain)),
+                event.emitter,
+            ),
+            Channel::new(event.bus, volume),
             DespawnOnExit(GameState::PLAYING),
         ));
+        attach(&mut entity, event.emitter);
+    }
+}
 
-        match event.setting.mode {
-            PlaybackMode::Loop => {
-                sound_bank.looping = true;
-            }
-            _ => {}
+fn control_loop(event: On<LoopEvent>, mut channels: Query<&mut Channel>) {
+    // the loop may already be gone, say after the shift ended
+    let Ok(mut channel) = channels.get_mut(event.handle.0) else {
+        return;
+    };
+    match event.command {
+        LoopCommand::Fade { volume, seconds } => {
+            channel.stopping = false;
+            channel.fade_to(volume, seconds);
+        }
+        LoopCommand::Speed(speed) => channel.speed = speed,
+        LoopCommand::Stop { seconds } => {
+            channel.stopping = true;
+            channel.fade_to(0.0, seconds);
         }
     }
 }
 
-fn stop_loop(
-    _event: On<StopLoopEvent>,
+fn duck(event: On<DuckEvent>, mut mixer: ResMut<Mixer>) {
+    mixer.duck_timer = Timer::from_seconds(event.seconds, TimerMode::Once);
+}
+
+// eases every channel towards its target and applies the bus volumes to the sinks
+fn mix(
     mut commands: Commands,
-    sounds: Query<(Entity, &PlaybackSettings), With<SoundComponent>>,
-    mut sound_bank: ResMut<SoundBank>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut mixer: ResMut<Mixer>,
+    mut channels: Query<(
+        Entity,
+        &mut Channel,
+        Option<&mut AudioSink>,
+        Option<&mut SpatialAudioSink>,
+    )>,
 ) {
-    for (entity, settings) in &sounds {
-        match settings.mode {
-            PlaybackMode::Loop => {
-                commands.entity(entity).despawn();
-                sound_bank.looping = false;
-            }
-            _ => {}
+    let delta = time.delta_secs();
+    mixer.duck_timer.tick(time.delta());
+    let duck_target = if mixer.duck_timer.is_finished() {
+        1.0
+    } else {
+        DUCK_LEVEL
+    };
+    mixer.duck_level = approach(mixer.duck_level, duck_target, DUCK_SPEED * delta);
+
+    for (entity, mut channel, sink, spatial_sink) in &mut channels {
+        channel.volume = if channel.rate > 0.0 {
+            approach(channel.volume, channel.target, channel.rate * delta)
+        } else {
+            channel.target
+        };
+        if channel.stopping && channel.volume <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let volume = Volume::Linear(channel.volume * mixer.gain(channel.bus, &settings));
+        if let Some(mut sink) = sink {
+            apply(&mut *sink, volume, channel.speed);
+        }
+        if let Some(mut sink) = spatial_sink {
+            apply(&mut *sink, volume, channel.speed);
         }
     }
 }
+
+fn apply(sink: &mut impl AudioSinkPlayback, volume: Volume, speed: f32) {
+    sink.set_volume(volume);
+    if sink.speed() != speed {
+        sink.set_speed(speed);
+    }
+}
+
+fn approach(current: f32, target: f32, step: f32) -> f32 {
+    if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    }
+}
@@ -0,0 +1,238 @@
+use bevy::{
+    input::mouse::{AccumulatedMouseScroll, MouseScrollUnit},
+    prelude::*,
+};
+
+use crate::{
+    GameState,
+    environment::{FORWARD_LOOK, PAGE_LOOK},
+    pen::Marker,
+    settings::Settings,
+};
+
+// where the player sits, every view but the desk is looked at from the chair
+pub const EYE: Vec3 = Vec3::new(0.0, 1.75, 0.0);
+// standing up a little to see the whole desk, shredder included
+const DESK_EYE: Vec3 = Vec3::new(0.0, 2.2, 0.0);
+const DESK_LOOK: Vec3 = Vec3::new(0.0, 0.75, 1.1);
+// between the boss and the planner
+const BOSS_LOOK: Vec3 = Vec3::new(-3.5, 1.4, 7.0);
+// how quickly the camera eases to a new view at the normal camera speed, higher is snappier
+const TRANSITION_SPEED: f32 = 5.0;
+// fraction of the screen at each edge where a lifted marker pans the view
+const EDGE_MARGIN: f32 = 0.08;
+// radians per second of panning at the very edge
+const FREE_LOOK_SPEED: f32 = 0.8;
+// how far free look may turn from the view, in radians
+const MAX_FREE_LOOK: Vec2 = Vec2::new(0.6, 0.35);
+// fully zoomed in, the camera has leaned this fraction of the way to the page
+const MAX_LEAN: f32 = 0.6;
+// zoom gained per line of the scroll wheel
+const ZOOM_STEP: f32 = 0.15;
+// touchpads scroll in pixels, this many make up a line
+const PIXELS_PER_LINE: f32 = 40.0;
+// held down to lean all the way in while it's pressed
+const LEAN_KEY: KeyCode = KeyCode::ShiftLeft;
+
+// The camera the player sees the office through
+#[derive(Component)]
+pub struct MainCamera;
+
+// A named place to look at from the desk
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Viewpoint {
+    // close-up on the page being worked on
+    Page,
+    Desk,
+    // the window and the mob behind it
+    Window,
+    // the boss and the planner
+    Boss,
+}
+
+impl Viewpoint {
+    const ALL: [Viewpoint; 4] = [
+        Viewpoint::Page,
+        Viewpoint::Desk,
+        Viewpoint::Window,
+        Viewpoint::Boss,
+    ];
+
+    fn eye(&self) -> Vec3 {
+        match self {
+            Viewpoint::Desk => DESK_EYE,
+            _ => EYE,
+        }
+    }
+
+    fn target(&self) -> Vec3 {
+        match self {
+            Viewpoint::Page => PAGE_LOOK,
+            Viewpoint::Desk => DESK_LOOK,
+            Viewpoint::Window => FORWARD_LOOK,
+            Viewpoint::Boss => BOSS_LOOK,
+        }
+    }
+
+    // number key that jumps straight to the view
+    fn key(&self) -> KeyCode {
+        match self {
+            Viewpoint::Page => KeyCode::Digit1,
+            Viewpoint::Desk => KeyCode::Digit2,
+            Viewpoint::Window => KeyCode::Digit3,
+            Viewpoint::Boss => KeyCode::Digit4,
+        }
+    }
+}
+
+// What the camera is looking at, and where it is heading
+#[derive(Resource)]
+pub struct CameraView {
+    pub viewpoint: Viewpoint,
+    // higher is snappier, TRANSITION_SPEED scaled by the camera speed setting
+    pub transition_speed: f32,
+    // yaw and pitch of the free look on top of the viewpoint
+    free_look: Vec2,
+    // how far the scroll wheel has zoomed in on the page, from 0 to 1
+    zoom: f32,
+    // how far the camera has actually leaned in, easing after the zoom
+    lean: f32,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self {
+            viewpoint: Viewpoint::Window,
+            transition_speed: TRANSITION_SPEED,
+            free_look: Vec2::ZERO,
+            zoom: 0.0,
+            lean: 0.0,
+        }
+    }
+}
+
+impl CameraView {
+    pub fn set(&mut self, viewpoint: Viewpoint) {
+        self.viewpoint = viewpoint;
+        self.free_look = Vec2::ZERO;
+        self.zoom = 0.0;
+    }
+
+    // leaning in brings the page closer, so the marker moves less for the same mouse motion
+    pub fn motion_scale(&self) -> f32 {
+        1.0 - self.lean * MAX_LEAN
+    }
+
+    // the camera transform the view eases towards
+    fn goal(&self) -> Transform {
+        let (eye, target) = (self.viewpoint.eye(), self.viewpoint.target());
+        let mut goal = Transform::from_translation(eye.lerp(target, self.lean * MAX_LEAN))
+            .looking_at(target, Vec3::Y);
+        goal.rotate_y(self.free_look.x);
+        goal.rotate_local_x(self.free_look.y);
+        goal
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CameraView>()
+        .add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (choose_view, zoom, free_look, move_camera)
+                .chain()
+                .run_if(in_state(GameState::PLAYING)),
+        );
+}
+
+// every shift starts facing the window, easing between views as quickly as the settings ask
+fn setup(mut view: ResMut<CameraView>, settings: Res<Settings>) {
+    view.set(Viewpoint::Window);
+    view.transition_speed = TRANSITION_SPEED * settings.camera_speed.factor();
+}
+
+// Space swaps between the page and the window, the number keys pick any view
+fn choose_view(keyboard_input: Res<ButtonInput<KeyCode>>, mut view: ResMut<CameraView>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        let next = if view.viewpoint == Viewpoint::Page {
+            Viewpoint::Window
+        } else {
+            Viewpoint::Page
+        };
+        view.set(next);
+    }
+    for viewpoint in Viewpoint::ALL {
+        if keyboard_input.just_pressed(viewpoint.key()) {
+            view.set(viewpoint);
+        }
+    }
+}
+
+// the scroll wheel zooms in on the page in steps, holding the lean key goes all the way
+fn zoom(scroll: Res<AccumulatedMouseScroll>, mut view: ResMut<CameraView>) {
+    if view.viewpoint != Viewpoint::Page {
+        return;
+    }
+    let lines = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / PIXELS_PER_LINE,
+    };
+    if lines != 0.0 {
+        view.zoom = (view.zoom + lines * ZOOM_STEP).clamp(0.0, 1.0);
+    }
+}
+
+// pushing the lifted marker against the edge of the screen turns the head that way
+fn free_look(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    marker: Single<&GlobalTransform, With<Marker>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut view: ResMut<CameraView>,
+) {
+    if !settings.free_look || mouse.pressed(MouseButton::Left) {
+        return;
+    }
+    let (camera, camera_transform) = camera.into_inner();
+    let (Ok(position), Some(size)) = (
+        camera.world_to_viewport(camera_transform, marker.translation()),
+        camera.logical_viewport_size(),
+    ) else {
+        return;
+    };
+    let fraction = position / size;
+    // how far into the margin the marker is on each axis, -1 at the left and top edges
+    let push = |value: f32| {
+        if value < EDGE_MARGIN {
+            -(1.0 - value / EDGE_MARGIN).min(1.0)
+        } else if value > 1.0 - EDGE_MARGIN {
+            ((value - (1.0 - EDGE_MARGIN)) / EDGE_MARGIN).min(1.0)
+        } else {
+            0.0
+        }
+    };
+    // screen right is a turn to the right, screen up is a tilt up
+    let turn = Vec2::new(-push(fraction.x), -push(fraction.y));
+    view.free_look = (view.free_look + turn * FREE_LOOK_SPEED * time.delta_secs())
+        .clamp(-MAX_FREE_LOOK, MAX_FREE_LOOK);
+}
+
+fn move_camera(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut view: ResMut<CameraView>,
+    mut camera_transform: Single<&mut Transform, With<MainCamera>>,
+) {
+    // eases out, quick at first and settling gently, whatever the frame rate
+    let blend = 1.0 - (-view.transition_speed * time.delta_secs()).exp();
+    let zoom = if view.viewpoint == Viewpoint::Page && keyboard_input.pressed(LEAN_KEY) {
+        1.0
+    } else {
+        view.zoom
+    };
+    view.lean += (zoom - view.lean) * blend;
+    let goal = view.goal();
+    camera_transform.translation = camera_transform.translation.lerp(goal.translation, blend);
+    camera_transform.rotation = camera_transform.rotation.slerp(goal.rotation, blend);
+}
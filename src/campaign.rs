@@ -0,0 +1,192 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{ActiveShift, GameMode, GameState, ShiftOutcome, ShiftResult, locale::Locale};
+
+const SAVE_DIR: &str = "saves";
+const SAVE_PATH: &str = "saves/campaign.txt";
+
+pub const DESK_LAMP_POS: Vec3 = Vec3::new(-0.8, 1.4, 1.6);
+
+// Tools and desk upgrades handed out at the end of a day
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Unlock {
+    // refills the marker twice as fast
+    InkBottle,
+    // wider marker tip, redacts more per stroke
+    ChiselTip,
+    // a lamp that lights up the page
+    DeskLamp,
+}
+
+impl Unlock {
+    // locale key of the display name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Unlock::InkBottle => "unlock-ink-bottle",
+            Unlock::ChiselTip => "unlock-chisel-tip",
+            Unlock::DeskLamp => "unlock-desk-lamp",
+        }
+    }
+}
+
+pub struct Mission {
+    // locale keys
+    pub title: &'static str,
+    pub directive: &'static str,
+    pub document: &'static str,
+    pub lifetime: f32,
+    // handed out when the day is completed
+    pub unlock: Option<Unlock>,
+}
+
+pub const MISSIONS: [Mission; 5] = [
+    Mission {
+        title: "day1-title",
+        directive: "day1-directive",
+        document: "assets/text/campaign/day1.txt",
+        lifetime: 90.0,
+        unlock: Some(Unlock::InkBottle),
+    },
+    Mission {
+        title: "day2-title",
+        directive: "day2-directive",
+        document: "assets/text/campaign/day2.txt",
+        lifetime: 80.0,
+        unlock: Some(Unlock::DeskLamp),
+    },
+    Mission {
+        title: "day3-title",
+        directive: "day3-directive",
+        document: "assets/text/campaign/day3.txt",
+        lifetime: 75.0,
+        unlock: Some(Unlock::ChiselTip),
+    },
+    Mission {
+        title: "day4-title",
+        directive: "day4-directive",
+        document: "assets/text/campaign/day4.txt",
+        lifetime: 70.0,
+        unlock: None,
+    },
+    Mission {
+        title: "day5-title",
+        directive: "day5-directive",
+        document: "assets/text/campaign/day5.txt",
+        lifetime: 60.0,
+        unlock: None,
+    },
+];
+
+// Saved between sessions
+#[derive(Resource, Default)]
+pub struct CampaignProgress {
+    // index into MISSIONS of the next day to play
+    pub day: usize,
+    // set when the last shift finished a day, for the end screen
+    pub last_unlock: Option<Unlock>,
+}
+
+impl CampaignProgress {
+    pub fn is_finished(&self) -> bool {
+        self.day >= MISSIONS.len()
+    }
+
+    pub fn can_continue(&self) -> bool {
+        self.day > 0 && !self.is_finished()
+    }
+
+    pub fn unlocks(&self) -> Vec<Unlock> {
+        MISSIONS
+            .iter()
+            .take(self.day)
+            .filter_map(|mission| mission.unlock)
+            .collect()
+    }
+
+    // builds the shift for the current day
+    pub fn active_shift(&self, locale: &Locale) -> ActiveShift {
+        let mission = &MISSIONS[self.day.min(MISSIONS.len() - 1)];
+        let day = locale.format(
+            "campaign-day",
+            &[
+                ("day", (self.day + 1).to_string()),
+                ("title", locale.get(mission.title)),
+            ],
+        );
+        ActiveShift {
+            mode: GameMode::Campaign,
+            document: mission.document.to_owned(),
+            directive: format!("{day}\n{}", locale.get(mission.directive)),
+            lifetime: mission.lifetime,
+            unlocks: self.unlocks(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct DeskLamp;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(load_progress())
+        .add_systems(OnEnter(GameState::PLAYING), setup_desk_upgrades)
+        .add_systems(OnExit(GameState::PLAYING), record_result);
+}
+
+fn load_progress() -> CampaignProgress {
+    let mut progress = CampaignProgress::default();
+    if let Ok(contents) = fs::read_to_string(SAVE_PATH) {
+        for line in contents.lines() {
+            if let Some(day) = line.strip_prefix("day=") {
+                progress.day = day.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    progress
+}
+
+pub fn save_progress(progress: &CampaignProgress) {
+    if let Err(err) = fs::create_dir_all(SAVE_DIR)
+        .and_then(|_| fs::write(SAVE_PATH, format!("day={}\n", progress.day)))
+    {
+        println!("Failed to save campaign: {err}");
+    }
+}
+
+fn setup_desk_upgrades(mut commands: Commands, active_shift: Res<ActiveShift>) {
+    if active_shift.has_unlock(Unlock::DeskLamp) {
+        commands.spawn((
+            DeskLamp,
+            SpotLight {
+                color: Color::srgb(1.0, 0.85, 0.6),
+                intensity: 200000.0,
+                range: 5.0,
+                shadows_enabled: true,
+                outer_angle: 0.8,
+                ..default()
+            },
+            Transform::from_translation(DESK_LAMP_POS)
+                .looking_at(Vec3::new(0.0, 0.8, 1.0), Vec3::Y),
+            DespawnOnExit(GameState::PLAYING),
+        ));
+    }
+}
+
+// advance to the next day if the shift was completed
+fn record_result(
+    active_shift: Res<ActiveShift>,
+    shift_result: Res<ShiftResult>,
+    mut progress: ResMut<CampaignProgress>,
+) {
+    progress.last_unlock = None;
+    if active_shift.mode != GameMode::Campaign || shift_result.outcome != ShiftOutcome::Completed {
+        return;
+    }
+
+    if let Some(mission) = MISSIONS.get(progress.day) {
+        progress.last_unlock = mission.unlock;
+    }
+    progress.day += 1;
+    save_progress(&progress);
+}
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+
+use crate::{
+    GameState,
+    audio::{Bus, Emitter, SoundEvent, Sounds},
+    pen::{InkSupplyPercent, Marker},
+};
+
+const CAP_KEY: KeyCode = KeyCode::Enter;
+// how long the felt can be left open before it starts to dry out
+const DRY_OUT_SECONDS: f32 = 20.0;
+// ink percent lost each second the felt is drying out
+const EVAPORATION_RATE: f32 = 2.0;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CapState {
+    Capped,
+    Uncapped,
+    // left open too long, the ink is evaporating
+    DryingOut,
+    // dipped in the ink well
+    Refilling,
+}
+
+impl CapState {
+    // only an open felt out of the ink well leaves ink on the page
+    pub fn can_draw(&self) -> bool {
+        matches!(self, CapState::Uncapped | CapState::DryingOut)
+    }
+}
+
+// Whether the marker's cap is on, and how long the felt has been open
+#[derive(Component)]
+pub struct MarkerCap {
+    state: CapState,
+    open: Timer,
+}
+
+impl Default for MarkerCap {
+    fn default() -> Self {
+        Self {
+            state: CapState::Uncapped,
+            open: Timer::from_seconds(DRY_OUT_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+impl MarkerCap {
+    pub fn capped() -> Self {
+        Self {
+            state: CapState::Capped,
+            ..default()
+        }
+    }
+
+    pub fn state(&self) -> CapState {
+        self.state
+    }
+}
+
+// Sent whenever the cap moves from one state to another
+#[derive(Event)]
+pub struct CapEvent {
+    pub from: CapState,
+    pub to: CapState,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (toggle_cap, update_cap)
+            .chain()
+            .run_if(in_state(GameState::PLAYING)),
+    )
+    .add_observer(click);
+}
+
+fn set_state(commands: &mut Commands, cap: &mut MarkerCap, state: CapState) {
+    if cap.state == state {
+        return;
+    }
+    // a fresh dip or taking the cap off wets the felt again
+    if state == CapState::Uncapped {
+        cap.open.reset();
+    }
+    commands.trigger(CapEvent {
+        from: cap.state,
+        to: state,
+    });
+    cap.state = state;
+}
+
+fn toggle_cap(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut cap: Single<&mut MarkerCap>,
+) {
+    if keyboard_input.just_pressed(CAP_KEY) {
+        let state = if cap.state == CapState::Capped {
+            CapState::Uncapped
+        } else {
+            CapState::Capped
+        };
+        set_state(&mut commands, &mut cap, state);
+    }
+}
+
+// the open felt dries out with time, dipping it refills it, leaving it open costs ink
+fn update_cap(
+    mut commands: Commands,
+    time: Res<Time>,
+    marker: Single<(&Marker, &mut MarkerCap, &mut InkSupplyPercent)>,
+) {
+    let (marker, mut cap, mut ink_supply) = marker.into_inner();
+    let state = match cap.state {
+        CapState::Capped => CapState::Capped,
+        _ if marker.in_ink_well() => CapState::Refilling,
+        CapState::Refilling => CapState::Uncapped,
+        CapState::Uncapped => {
+            cap.open.tick(time.delta());
+            if cap.open.is_finished() {
+                CapState::DryingOut
+            } else {
+                CapState::Uncapped
+            }
+        }
+        CapState::DryingOut => {
+            ink_supply.0 = (ink_supply.0 - EVAPORATION_RATE * time.delta_secs()).max(0.0);
+            CapState::DryingOut
+        }
+    };
+    set_state(&mut commands, &mut cap, state);
+}
+
+// the cap snaps on and off at the marker
+fn click(event: On<CapEvent>, mut commands: Commands, marker: Single<Entity, With<Marker>>) {
+    if (event.from == CapState::Capped) != (event.to == CapState::Capped) {
+        commands.trigger(SoundEvent {
+            sound: Sounds::MarkerCap,
+            bus: Bus::Sfx,
+            emitter: Emitter::Follow(*marker),
+            setting: PlaybackSettings::DESPAWN,
+        });
+    }
+}
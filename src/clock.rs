@@ -5,6 +5,9 @@ use bevy::prelude::*;
 use bevy::{color::palettes::css, math::ops::floor};
 use bevy_rich_text3d::{Text3d, Text3dBounds, Text3dStyling, TextAtlas, Weight};
 
+use crate::paper::PageTimer;
+use crate::pen::Surface;
+use crate::settings::Settings;
 use crate::{CountdownTimer, GameState};
 
 const ALARM_CLOCK_MODEL_PATH: &str = "models/alarm_clock.glb";
@@ -17,10 +20,27 @@ pub(super) fn plugin(app: &mut App) {
         .add_systems(Update, update_clock.run_if(in_state(GameState::PLAYING)));
 }
 
+// seconds left on the shift, or on the current page's deadline when page timers are on
+pub fn time_left(countdown: &CountdownTimer, page_timer: &PageTimer, settings: &Settings) -> f32 {
+    if settings.page_timers {
+        page_timer.timer.remaining_secs()
+    } else {
+        countdown.0.remaining_secs()
+    }
+}
+
+pub fn format_time(remaining_secs: f32) -> String {
+    let countdown = floor(remaining_secs) as u32;
+    let minutes = countdown / 60;
+    let seconds = countdown - (minutes * 60);
+    format!("{:02}:{:02}", minutes, seconds)
+}
+
 fn setup_mesh(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<Settings>,
 ) {
     let alarm_clock_scene =
         SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(ALARM_CLOCK_MODEL_PATH)));
@@ -32,6 +52,7 @@ fn setup_mesh(
     commands.spawn((
         alarm_clock_scene,
         alarm_clock_transform,
+        Surface,
         DespawnOnExit(GameState::PLAYING),
     ));
 
@@ -63,6 +84,12 @@ fn setup_mesh(
         })),
         text_transform,
         Mesh3d::default(),
+        // the clock face stays blank when the time is only on screen
+        if settings.readouts.diegetic() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        },
         DespawnOnExit(GameState::PLAYING),
     ));
     commands.spawn((
@@ -83,15 +110,15 @@ fn setup_mesh(
 
 fn update_clock(
     timer: Res<CountdownTimer>,
+    page_timer: Res<PageTimer>,
+    settings: Res<Settings>,
     mut alarm_clock_text: Single<(&mut Text3d, &mut Transform), With<AlarmClockText>>,
     mut alarm_color_light: Single<&mut SpotLight, With<AlarmClockText>>,
 ) {
-    let countdown = floor(timer.0.remaining_secs()) as u32;
-    let minutes = countdown / 60;
-    let seconds = countdown - (minutes * 60);
-    alarm_clock_text.0.segments = Text3d::new(format!("{:02}:{:02}", minutes, seconds)).segments;
+    let remaining_secs = time_left(&timer, &page_timer, &settings);
+    alarm_clock_text.0.segments = Text3d::new(format_time(remaining_secs)).segments;
 
-    let t = timer.0.remaining_secs() - countdown as f32;
+    let t = remaining_secs - floor(remaining_secs);
     let pulse = sin(t * PI);
     alarm_clock_text.1.scale = Vec3::splat(0.0075 + pulse * 0.001);
     alarm_color_light.intensity = 7500.0 + 7500.0 * pulse;
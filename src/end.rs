@@ -1,12 +1,20 @@
 use bevy::{app::AppExit, color, prelude::*};
 
-use crate::paper::{Page, PageScores};
+use crate::{
+    ActiveShift, GameMode, ShiftOutcome, ShiftResult,
+    campaign::CampaignProgress,
+    locale::{Locale, UI_FONT_PATH},
+    paper::{Page, PageScores, PageTimer},
+    procgen::HighScores,
+    settings::Settings,
+};
 
 use super::GameState;
 
 #[derive(Component)]
 enum MenuAction {
     Replay,
+    NextDay,
     Quit,
 }
 
@@ -22,6 +30,7 @@ struct SelectedOption;
 const BG_COLOR: Color = Color::srgb(0.25, 0.5, 0.9);
 const SUB_COLOR: Color = Color::srgb(0.65, 0.7, 0.8);
 const TITLE_COLOR: Color = Color::Srgba(color::palettes::css::RED);
+const WIN_COLOR: Color = Color::Srgba(color::palettes::css::LIME);
 const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
@@ -36,7 +45,18 @@ pub(super) fn plugin(app: &mut App) {
         );
 }
 
-fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>, page_score: Res<PageScores>) {
+fn menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    page_score: Res<PageScores>,
+    shift_result: Res<ShiftResult>,
+    active_shift: Res<ActiveShift>,
+    progress: Res<CampaignProgress>,
+    high_scores: Res<HighScores>,
+    page_timer: Res<PageTimer>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+) {
     commands.spawn((DespawnOnExit(GameState::END), Camera2d));
 
     // Common style for all buttons on the screen
@@ -56,7 +76,9 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>, page_score
         left: px(10),
         ..default()
     };
+    let ui_font: Handle<Font> = asset_server.load(UI_FONT_PATH);
     let button_text_font = TextFont {
+        font: ui_font.clone(),
         font_size: 33.0,
         ..default()
     };
@@ -68,90 +90,201 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>, page_score
 
     let characters_redacted = page_score.correctly_redacted;
     let characters_missed = page_score.total_to_redact - page_score.correctly_redacted;
+    let time_bonus = shift_result.time_bonus;
+
+    let (title, subtitle, title_color) = match shift_result.outcome {
+        ShiftOutcome::Leaked => (
+            locale.get("end-leaked-title"),
+            locale.get("end-leaked-subtitle"),
+            TITLE_COLOR,
+        ),
+        ShiftOutcome::Completed => (
+            locale.get("end-complete-title"),
+            locale.get("end-complete-subtitle"),
+            WIN_COLOR,
+        ),
+    };
+
+    let mut stats = vec![
+        locale.format(
+            "end-redacted",
+            &[("count", characters_redacted.to_string())],
+        ),
+        locale.format("end-missed", &[("count", characters_missed.to_string())]),
+        locale.format("end-time-bonus", &[("bonus", time_bonus.to_string())]),
+    ];
+
+    let stamps = [
+        page_score.correct_stamps,
+        page_score.wrong_stamps,
+        page_score.missed_stamps,
+    ];
+    if stamps.iter().any(|&count| count > 0) {
+        stats.push(locale.format(
+            "end-stamps",
+            &[
+                ("correct", stamps[0].to_string()),
+                ("wrong", stamps[1].to_string()),
+                ("missed", stamps[2].to_string()),
+            ],
+        ));
+    }
 
-    commands.spawn((
-        DespawnOnExit(GameState::END),
-        Node {
-            width: percent(100),
-            height: percent(100),
+    let shreds = [
+        page_score.correct_shreds,
+        page_score.wrong_shreds,
+        page_score.missed_shreds,
+    ];
+    if shreds.iter().any(|&count| count > 0) {
+        stats.push(locale.format(
+            "end-shreds",
+            &[
+                ("correct", shreds[0].to_string()),
+                ("wrong", shreds[1].to_string()),
+                ("missed", shreds[2].to_string()),
+            ],
+        ));
+    }
+
+    if settings.page_timers {
+        stats.push(locale.format(
+            "end-leaked-targets",
+            &[("count", page_score.leaked.to_string())],
+        ));
+        stats.push(locale.format(
+            "end-late-pages",
+            &[("count", page_timer.late_pages.to_string())],
+        ));
+    }
+    if active_shift.mode == GameMode::Endless {
+        stats.push(locale.format(
+            "end-score",
+            &[("score", high_scores.last_score.to_string())],
+        ));
+        if let Some(rank) = high_scores.last_rank {
+            stats.push(locale.format("end-new-high-score", &[("rank", (rank + 1).to_string())]));
+        }
+        stats.push(locale.get("end-high-scores"));
+        for (i, score) in high_scores.scores.iter().enumerate() {
+            stats.push(locale.format(
+                "end-high-score-entry",
+                &[("rank", (i + 1).to_string()), ("score", score.to_string())],
+            ));
+        }
+    }
+
+    // the main button replays the shift, or moves the campaign along
+    let mut buttons = vec![(MenuAction::Replay, "button-replay")];
+    if active_shift.mode == GameMode::Campaign {
+        if let Some(unlock) = progress.last_unlock {
+            stats.push(locale.format("end-unlocked", &[("unlock", locale.get(unlock.name_key()))]));
+        }
+        if progress.is_finished() {
+            stats.push(locale.get("end-campaign-complete"));
+            buttons = vec![(MenuAction::Replay, "button-main-menu")];
+        } else if shift_result.outcome == ShiftOutcome::Completed {
+            buttons = vec![
+                (MenuAction::NextDay, "button-next-day"),
+                (MenuAction::Replay, "button-main-menu"),
+            ];
+        } else {
+            buttons = vec![
+                (MenuAction::NextDay, "button-retry-day"),
+                (MenuAction::Replay, "button-main-menu"),
+            ];
+        }
+    }
+
+    let column = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
             align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
             ..default()
-        },
-        ImageNode::new(background_image.clone()),
-        children![(
-            Node {
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            children![
-                // Display the game name
-                (
-                    Text::new("CONFIDENTIAL INFORMATION LEAKED"),
-                    TextFont {
-                        font_size: 67.0,
-                        ..default()
-                    },
-                    TextColor(TITLE_COLOR),
-                    Node {
-                        margin: UiRect::all(px(25)),
-                        ..default()
-                    },
-                ),
-                (
-                    Text::new("(a fate worse than death)"),
-                    TextFont {
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(TITLE_COLOR),
-                    Node { ..default() },
-                ),
-                (
-                    Text::new(format!("Characters Redacted: {characters_redacted}")),
+        })
+        .with_children(|parent| {
+            // Display the game name
+            parent.spawn((
+                Text::new(title),
+                TextFont {
+                    font: ui_font.clone(),
+                    font_size: 67.0,
+                    ..default()
+                },
+                TextColor(title_color),
+                Node {
+                    margin: UiRect::all(px(25)),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(subtitle),
+                TextFont {
+                    font: ui_font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(title_color),
+                Node { ..default() },
+            ));
+            for line in stats {
+                parent.spawn((
+                    Text::new(line),
                     TextFont {
+                        font: ui_font.clone(),
                         font_size: 16.0,
                         ..default()
                     },
-                    TextColor(TITLE_COLOR),
-                ),
-                (
-                    Text::new(format!("Characters Missed: {characters_missed}")),
-                    TextFont {
-                        font_size: 16.0,
-                        ..default()
-                    },
-                    TextColor(TITLE_COLOR),
-                ),
-                // Display Buttons
-                (
+                    TextColor(title_color),
+                ));
+            }
+
+            // Display Buttons
+            for (action, label) in buttons {
+                parent.spawn((
                     Button,
                     button_node.clone(),
                     BackgroundColor(NORMAL_BUTTON),
-                    MenuAction::Replay,
+                    action,
                     children![
-                        (ImageNode::new(right_icon), button_icon_node.clone()),
+                        (ImageNode::new(right_icon.clone()), button_icon_node.clone()),
                         (
-                            Text::new("Replay"),
+                            Text::new(locale.get(label)),
                             button_text_font.clone(),
                             TextColor(TEXT_COLOR),
                         ),
-                    ]
-                ),
-                (
-                    Button,
-                    button_node,
-                    BackgroundColor(NORMAL_BUTTON),
-                    MenuAction::Quit,
-                    children![
-                        (ImageNode::new(exit_icon), button_icon_node),
-                        (Text::new("Quit"), button_text_font, TextColor(TEXT_COLOR),),
-                    ]
-                ),
-            ]
-        )],
-    ));
+                    ],
+                ));
+            }
+            parent.spawn((
+                Button,
+                button_node,
+                BackgroundColor(NORMAL_BUTTON),
+                MenuAction::Quit,
+                children![
+                    (ImageNode::new(exit_icon), button_icon_node),
+                    (
+                        Text::new(locale.get("button-quit")),
+                        button_text_font,
+                        TextColor(TEXT_COLOR),
+                    ),
+                ],
+            ));
+        })
+        .id();
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::END),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ImageNode::new(background_image.clone()),
+        ))
+        .add_child(column);
 }
 
 // This system handles changing all buttons color based on mouse interaction
@@ -176,6 +309,9 @@ fn menu_action(
     interaction_query: Query<(&Interaction, &MenuAction), (Changed<Interaction>, With<Button>)>,
     mut app_exit_writer: MessageWriter<AppExit>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut active_shift: ResMut<ActiveShift>,
+    progress: Res<CampaignProgress>,
+    locale: Res<Locale>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -188,6 +324,10 @@ fn menu_action(
                 MenuAction::Replay => {
                     game_state.set(GameState::MENU);
                 }
+                MenuAction::NextDay => {
+                    *active_shift = progress.active_shift(&locale);
+                    game_state.set(GameState::PLAYING);
+                }
             }
         }
     }
@@ -2,14 +2,20 @@ use bevy::image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamp
 use bevy::math::Affine2;
 use bevy::math::ops::floor;
 use bevy::prelude::*;
+use bevy::ui::IsDefaultUiCamera;
 use bevy_sprite3d::{Sprite3d, Sprite3dPlugin};
 
 use super::GameState;
-use crate::audio::{SoundBank, SoundEvent, Sounds};
+use crate::audio::{Bus, EAR_GAP, Emitter, LoopCommand, LoopEvent, SoundEvent, Sounds};
+use crate::camera::{EYE, MainCamera};
 use crate::loading::GameAssets;
-use crate::{CountdownTimer, LIFETIME};
+use crate::mob::MobAmbience;
+use crate::pen::Surface;
+use crate::{ActiveShift, CountdownTimer};
 
 pub const PIXELS_PER_METRE: f32 = 30.0;
+// the crack stage where the window gives way
+pub const GLASS_SHATTER_STAGE: usize = 8;
 
 #[derive(Component)]
 struct GlassCrackWall;
@@ -20,38 +26,20 @@ pub struct GlassCrackStage(pub usize);
 #[derive(Resource)]
 pub struct LastCrackStage(pub usize);
 
-#[derive(Resource)]
-struct LookingAt {
-    pub vec: Vec3,
-    pub look: Looks,
-}
-
 #[derive(Component)]
 pub struct Desk;
 
-#[derive(Component)]
-pub struct MobSound;
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
-pub enum Looks {
-    Page,
-    #[default]
-    Forward,
-}
-
 pub const PAGE_LOOK: Vec3 = Vec3::new(0.0, 0.9, 1.0);
 pub const FORWARD_LOOK: Vec3 = Vec3::new(0.0, 1.25, 10.0);
+// how long the mob takes to swell to the next crack stage's volume
+const MOB_FADE_SECONDS: f32 = 2.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(Sprite3dPlugin)
         .insert_resource(GlassCrackStage(0))
         .insert_resource(LastCrackStage(0))
-        .insert_resource(LookingAt {
-            vec: FORWARD_LOOK,
-            look: Looks::Forward,
-        })
         .add_systems(OnEnter(GameState::PLAYING), setup)
-        .add_systems(Update, (update_glass_cracks, update_looking));
+        .add_systems(Update, update_glass_cracks);
 }
 
 fn setup(
@@ -60,21 +48,17 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut countdown: ResMut<CountdownTimer>,
-    mut looking_at: ResMut<LookingAt>,
+    active_shift: Res<ActiveShift>,
     mut glass_crack_stage: ResMut<GlassCrackStage>,
     mut glass_crack_prev: ResMut<LastCrackStage>,
 ) {
     // reset timer
-    countdown.0 = Timer::from_seconds(LIFETIME, TimerMode::Once);
+    countdown.0 = Timer::from_seconds(active_shift.lifetime, TimerMode::Once);
 
     // set glass crack ani stage
     glass_crack_stage.0 = 0;
     glass_crack_prev.0 = 0;
 
-    // reset look
-    looking_at.vec = FORWARD_LOOK;
-    looking_at.look = Looks::Forward;
-
     commands.spawn((
         Sprite::from_image(assets.wall.clone()),
         Sprite3d {
@@ -107,6 +91,7 @@ fn setup(
         Transform::from_xyz(0.0, 0.70, 1.0),
         DespawnOnExit(GameState::PLAYING),
         Desk,
+        Surface,
     ));
 
     // Light
@@ -122,7 +107,11 @@ fn setup(
     // Camera
     commands.spawn((
         Camera3d::default(),
-        Transform::from_xyz(0.0, 1.75, 0.0).looking_at(PAGE_LOOK, Vec3::Y),
+        MainCamera,
+        // the overlay is drawn here rather than in the magnifier, which renders later
+        IsDefaultUiCamera,
+        Transform::from_translation(EYE).looking_at(PAGE_LOOK, Vec3::Y),
+        SpatialListener::new(EAR_GAP),
         DespawnOnExit(GameState::PLAYING),
     ));
 }
@@ -134,85 +123,45 @@ fn update_glass_cracks(
     mut glass_crack_prev: ResMut<LastCrackStage>,
     mut query: Query<&mut Sprite, With<GlassCrackWall>>,
     assets: Res<GameAssets>,
-    sound_bank: ResMut<SoundBank>,
-    sounds: Query<Entity, With<MobSound>>,
+    mob_ambience: Option<Res<MobAmbience>>,
 ) {
-    let progress = timer.0.elapsed_secs() / LIFETIME;
+    let progress = timer.0.elapsed_secs() / timer.0.duration().as_secs_f32();
     glass_crack_stage.0 = (floor(progress * assets.glass_cracks.len() as f32) as usize)
         .clamp(0, assets.glass_cracks.len() - 1);
 
     // trigger crack sound on stage change
     if glass_crack_prev.0 != glass_crack_stage.0 {
         glass_crack_prev.0 = glass_crack_stage.0;
-        if glass_crack_stage.0 < 8 {
+        if glass_crack_stage.0 < GLASS_SHATTER_STAGE {
             commands.trigger(SoundEvent {
                 sound: Sounds::GlassCrack,
+                bus: Bus::Sfx,
+                emitter: Emitter::At(FORWARD_LOOK),
                 setting: PlaybackSettings::ONCE,
             });
-        } else if glass_crack_stage.0 == 8 {
+        } else if glass_crack_stage.0 == GLASS_SHATTER_STAGE {
             commands.trigger(SoundEvent {
                 sound: Sounds::GlassShatter,
+                bus: Bus::Sfx,
+                emitter: Emitter::At(FORWARD_LOOK),
                 setting: PlaybackSettings::ONCE,
             });
         }
 
-        // reset mob sound
-        if let Some(handle) = sound_bank.sounds.get(&Sounds::Mob) {
-            for entity in &sounds {
-                commands.entity(entity).despawn();
+        // the mob swells with each stage
+        if let Some(mob_ambience) = mob_ambience {
+            for &handle in &mob_ambience.0 {
+                commands.trigger(LoopEvent {
+                    handle,
+                    command: LoopCommand::Fade {
+                        volume: progress,
+                        seconds: MOB_FADE_SECONDS,
+                    },
+                });
             }
-
-            commands.spawn((
-                AudioPlayer::new(handle.clone()),
-                PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(progress)),
-                MobSound,
-                DespawnOnExit(GameState::PLAYING),
-            ));
         }
     }
     for mut sprite in &mut query {
         sprite.image = assets.glass_cracks[glass_crack_stage.0].clone();
     }
 }
-
-fn update_looking(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut looking_at: ResMut<LookingAt>,
-    time: Res<Time>,
-    mut camera_transform: Single<&mut Transform, With<Camera3d>>,
-) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        match looking_at.look {
-            Looks::Forward => {
-                looking_at.look = Looks::Page;
-                looking_at.vec = PAGE_LOOK;
-            }
-            Looks::Page => {
-                looking_at.look = Looks::Forward;
-                looking_at.vec = FORWARD_LOOK;
-            }
-        }
-    }
-
-    // if keyboard_input.pressed(KeyCode::ArrowUp) {
-    //     looking_at.vec.y += time.delta_secs() * 2.0;
-    // }
-    // if keyboard_input.pressed(KeyCode::ArrowDown) {
-    //     looking_at.vec.y -= time.delta_secs() * 2.0;
-    // }
-    // if keyboard_input.pressed(KeyCode::ArrowLeft) {
-    //     looking_at.vec.x += time.delta_secs() * 2.0;
-    // }
-    // if keyboard_input.pressed(KeyCode::ArrowRight) {
-    //     looking_at.vec.x -= time.delta_secs() * 2.0;
-    // }
-
-    // interpolate camera move
-    let speed = 5.0;
-    let target_rotation = camera_transform
-        .looking_at(looking_at.vec, Vec3::Y)
-        .rotation;
-    camera_transform.rotation = camera_transform
-        .rotation
-        .slerp(target_rotation, speed * time.delta_secs());
-}
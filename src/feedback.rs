@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     GameState,
-    audio::{SoundEvent, Sounds},
+    audio::{Bus, Emitter, SoundEvent, Sounds},
 };
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -46,6 +46,8 @@ fn spawn_feedback(
         Feedbacks::Correct => {
             commands.trigger(SoundEvent {
                 sound: Sounds::Correct,
+                bus: Bus::Ui,
+                emitter: Emitter::Flat,
                 setting: PlaybackSettings::ONCE,
             });
             let material = materials.add(StandardMaterial {
@@ -87,6 +89,8 @@ fn spawn_feedback(
         Feedbacks::Wrong => {
             commands.trigger(SoundEvent {
                 sound: Sounds::Wrong,
+                bus: Bus::Ui,
+                emitter: Emitter::Flat,
                 setting: PlaybackSettings::ONCE,
             });
             let mesh = meshes.add(Cuboid::new(0.1, 1.0, 0.1));
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveShift, CountdownTimer, GameMode, GameState,
+    clock::{format_time, time_left},
+    locale::{Locale, UI_FONT_PATH},
+    music::Tension,
+    paper::{Page, PageScores, PageTimer},
+    pen::InkSupplyPercent,
+    procgen::EndlessRun,
+    settings::Settings,
+};
+
+const HUD_FONT_SIZE: f32 = 24.0;
+const HUD_TEXT_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+const HUD_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.55);
+
+// One line of the overlay, filled in every frame
+#[derive(Component, Debug, PartialEq, Eq, Copy, Clone)]
+enum HudReadout {
+    Time,
+    Page,
+    Targets,
+    Ink,
+    Outrage,
+    // only in endless runs
+    Score,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(Update, update_hud.run_if(in_state(GameState::PLAYING)));
+}
+
+// the overlay is only built when the settings ask for it
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    active_shift: Res<ActiveShift>,
+) {
+    if !settings.readouts.hud() {
+        return;
+    }
+    let font = TextFont {
+        font: asset_server.load(UI_FONT_PATH),
+        font_size: HUD_FONT_SIZE,
+        ..default()
+    };
+    let mut readouts = vec![
+        HudReadout::Time,
+        HudReadout::Page,
+        HudReadout::Targets,
+        HudReadout::Ink,
+        HudReadout::Outrage,
+    ];
+    if active_shift.mode == GameMode::Endless {
+        readouts.push(HudReadout::Score);
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: px(12),
+                left: px(12),
+                padding: UiRect::axes(px(12), px(8)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(HUD_BACKGROUND),
+            DespawnOnExit(GameState::PLAYING),
+        ))
+        .with_children(|parent| {
+            for readout in readouts {
+                parent.spawn((
+                    Text::default(),
+                    font.clone(),
+                    TextColor(HUD_TEXT_COLOR),
+                    readout,
+                ));
+            }
+        });
+}
+
+fn update_hud(
+    countdown: Res<CountdownTimer>,
+    page_timer: Res<PageTimer>,
+    page_scores: Res<PageScores>,
+    settings: Res<Settings>,
+    tension: Res<Tension>,
+    endless_run: Res<EndlessRun>,
+    locale: Res<Locale>,
+    page: Single<&Page>,
+    ink_supply: Single<&InkSupplyPercent>,
+    mut lines: Query<(&mut Text, &HudReadout)>,
+) {
+    for (mut text, readout) in &mut lines {
+        let line = match readout {
+            HudReadout::Time => locale.format(
+                "hud-time",
+                &[(
+                    "time",
+                    format_time(time_left(&countdown, &page_timer, &settings)),
+                )],
+            ),
+            HudReadout::Page => locale.format(
+                "planner-page",
+                &[
+                    ("page", (page.page_num + 1).to_string()),
+                    ("pages", page.pages.pages.len().to_string()),
+                ],
+            ),
+            HudReadout::Targets => locale.format(
+                "hud-targets",
+                &[(
+                    "count",
+                    (page_scores.page_total - page_scores.page_redaction).to_string(),
+                )],
+            ),
+            HudReadout::Ink => locale.format(
+                "hud-ink",
+                &[("ink", (ink_supply.0.round() as u32).to_string())],
+            ),
+            HudReadout::Outrage => locale.format(
+                "hud-outrage",
+                &[("outrage", ((tension.0 * 100.0).round() as u32).to_string())],
+            ),
+            HudReadout::Score => locale.format(
+                "planner-score",
+                &[("score", endless_run.score(&page_scores).to_string())],
+            ),
+        };
+        // only touch the text when it changes, so the layout isn't redone every frame
+        if text.0 != line {
+            text.0 = line;
+        }
+    }
+}
@@ -0,0 +1,1050 @@
+use std::fs;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use ttf_parser::{Face, GlyphId};
+use unicode_bidi::{Level, ParagraphBidiInfo};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    stamp::StampKind,
+    table::RedactionRule,
+    template::{DEFAULT_PHOTO, Ink, LineStyle, Template},
+};
+
+pub const PAPER_FONT_PATH: &str = "assets/fonts/SpaceMono-Regular.ttf";
+pub const PAPER_FONT_FAMILY: &str = "Space Mono";
+
+// world size of one em, space mono's 0.612em advance is the old 0.022 column
+const EM_SIZE: f32 = 0.022 / 0.612;
+// page text is drawn at TEXT_SIZE and scaled by TEXT_SCALE, which makes one em EM_SIZE wide
+// so the drawn glyphs line up with the index
+pub const TEXT_SCALE: f32 = 0.0025;
+pub const TEXT_SIZE: f32 = EM_SIZE / TEXT_SCALE;
+// advance used when the font can't be read
+const DEFAULT_ADVANCE: f32 = 0.612;
+
+pub const LINE_WIDTH: f32 = 24.0 * 0.022;
+pub const LINE_HEIGHT: f32 = 0.032;
+// centre of the lowest line that fits on a page, 26 typed lines
+pub const PAGE_HEIGHT: f32 = 25.0 * LINE_HEIGHT;
+// float slack so a line of exactly LINE_WIDTH still fits
+const FIT_TOLERANCE: f32 = 0.0001;
+// width of a hit-testing cell, one column of the monospace paper font
+const CELL_WIDTH: f32 = 0.022;
+
+// words with a meaning of their own in documents
+const LINE_BREAK: &str = "//";
+const RULE: &str = "---";
+const CELL_BREAK: &str = "|";
+// `[image <path> <columns>x<lines> rect:x,y,w,h poly:x,y,x,y,... ]`, region corners are fractions of the image
+const IMAGE_OPEN: &str = "[image";
+const IMAGE_CLOSE: &str = "]";
+// `[stamp:approved]` asks for a stamp on the page the word lands on, `[shred]` for the shredder
+const STAMP_OPEN: &str = "[stamp:";
+const SHRED_MARK: &str = "[shred]";
+const PAGE_BREAK: &str = "[page]";
+const RULE_THICKNESS: f32 = 0.0015;
+const CELL_PADDING: f32 = 0.006;
+
+// One grapheme placed on a page, positions are in world units
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    pub text: String,
+    // left edge measured from the start of the page, in visual order
+    pub x: f32,
+    // centre of the glyph down the page
+    pub y: f32,
+    pub advance: f32,
+    pub height: f32,
+    pub to_redact: bool,
+}
+
+impl Glyph {
+    pub fn centre(&self) -> f32 {
+        self.x + self.advance * 0.5
+    }
+
+    // centre of the glyph along the line and down the page
+    pub fn offset(&self) -> Vec2 {
+        Vec2::new(self.centre(), self.y)
+    }
+}
+
+// One line in logical order, the text renderer does its own bidi reordering
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub text: String,
+    // left edge of the line from the start of the page
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub scale: f32,
+    pub ink: Ink,
+    // radians the line is turned off level, positive runs downhill
+    pub tilt: f32,
+}
+
+impl Line {
+    pub fn centre(&self) -> f32 {
+        self.x + self.width * 0.5
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Photo {
+    pub rect: Rect,
+    pub image: String,
+    // parts of the print that have to be inked over, as polygons in page offsets
+    pub regions: Vec<Vec<Vec2>>,
+}
+
+// A page is drawn line by line, the glyphs are kept for hit-testing and scoring
+#[derive(Debug, Clone)]
+pub struct PageLayout {
+    pub template: Template,
+    pub lines: Vec<Line>,
+    pub glyphs: Vec<Glyph>,
+    // ruled lines and table borders, as thin rectangles
+    pub rules: Vec<Rect>,
+    pub photos: Vec<Photo>,
+    pub grid: GlyphGrid,
+    // stamps the page has to be given before it is filed
+    pub required_stamps: Vec<StampKind>,
+    // the page is to be destroyed rather than filed
+    pub must_shred: bool,
+}
+
+impl PageLayout {
+    fn new(template: Template) -> Self {
+        Self {
+            template,
+            lines: Vec::new(),
+            glyphs: Vec::new(),
+            rules: Vec::new(),
+            photos: Vec::new(),
+            grid: GlyphGrid::default(),
+            required_stamps: Vec::new(),
+            must_shred: false,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.glyphs.is_empty() && self.photos.is_empty()
+    }
+}
+
+// Glyph indices bucketed by row and column so strokes only test nearby glyphs
+#[derive(Debug, Clone, Default)]
+pub struct GlyphGrid {
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl GlyphGrid {
+    // glyphs are added to every cell they cover
+    fn insert(&mut self, index: usize, glyph: &Glyph) {
+        let first_column = (glyph.x / CELL_WIDTH).floor() as i32;
+        let last_column = ((glyph.x + glyph.advance) / CELL_WIDTH).ceil() as i32 - 1;
+        let first_row = grid_row(glyph.y - glyph.height * 0.5);
+        let last_row = grid_row(glyph.y + glyph.height * 0.5);
+        for row in first_row..=last_row {
+            for column in first_column..=last_column.max(first_column) {
+                self.cells.entry((row, column)).or_default().push(index);
+            }
+        }
+    }
+
+    // glyphs in the cells a stroke of `radius` from `from` to `to` can reach,
+    // points are page offsets like `Glyph::offset`
+    pub fn query_stroke(&self, from: Vec2, to: Vec2, radius: f32) -> Vec<usize> {
+        let min = from.min(to) - Vec2::splat(radius);
+        let max = from.max(to) + Vec2::splat(radius);
+        // a cell can be touched if the stroke passes within radius of any corner
+        let reach = radius + Vec2::new(CELL_WIDTH, LINE_HEIGHT).length() * 0.5;
+
+        let mut indices = Vec::new();
+        for row in grid_row(min.y)..=grid_row(max.y) {
+            for column in (min.x / CELL_WIDTH).floor() as i32..=(max.x / CELL_WIDTH).floor() as i32
+            {
+                let centre =
+                    Vec2::new((column as f32 + 0.5) * CELL_WIDTH, row as f32 * LINE_HEIGHT);
+                if distance_to_segment(centre, from, to) > reach {
+                    continue;
+                }
+                if let Some(cell) = self.cells.get(&(row, column)) {
+                    indices.extend_from_slice(cell);
+                }
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+// grid rows are centred on the typed lines
+fn grid_row(y: f32) -> i32 {
+    (y / LINE_HEIGHT).round() as i32
+}
+
+pub fn distance_to_segment(point: Vec2, from: Vec2, to: Vec2) -> f32 {
+    let segment = to - from;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 {
+        ((point - from).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(from + segment * t)
+}
+
+// Horizontal advances of the paper font, in ems
+#[derive(Resource)]
+pub struct FontMetrics {
+    advances: HashMap<char, f32>,
+    // the font's .notdef advance, used for characters it doesn't cover
+    fallback: f32,
+}
+
+impl Default for FontMetrics {
+    fn default() -> Self {
+        Self {
+            advances: HashMap::new(),
+            fallback: DEFAULT_ADVANCE,
+        }
+    }
+}
+
+impl FontMetrics {
+    pub fn load(path: &str) -> Self {
+        let Ok(data) = fs::read(path) else {
+            println!("Missing paper font {path}, using fixed advances");
+            return Self::default();
+        };
+        let Ok(face) = Face::parse(&data, 0) else {
+            println!("Can't parse paper font {path}, using fixed advances");
+            return Self::default();
+        };
+
+        let units_per_em = face.units_per_em() as f32;
+        let mut advances = HashMap::new();
+        if let Some(cmap) = face.tables().cmap {
+            for subtable in cmap.subtables {
+                if !subtable.is_unicode() {
+                    continue;
+                }
+                subtable.codepoints(|code_point| {
+                    let advance = subtable
+                        .glyph_index(code_point)
+                        .and_then(|glyph| face.glyph_hor_advance(glyph));
+                    if let (Some(c), Some(advance)) = (char::from_u32(code_point), advance) {
+                        advances.insert(c, advance as f32 / units_per_em);
+                    }
+                });
+            }
+        }
+        let fallback = face
+            .glyph_hor_advance(GlyphId(0))
+            .map(|advance| advance as f32 / units_per_em)
+            .unwrap_or(DEFAULT_ADVANCE);
+
+        Self { advances, fallback }
+    }
+
+    // world width of a grapheme cluster, combining marks the font covers have no advance
+    // and ones it doesn't are drawn over the base character
+    pub fn advance(&self, grapheme: &str) -> f32 {
+        let mut chars = grapheme.chars();
+        let Some(base) = chars.next() else {
+            return 0.0;
+        };
+        let base_advance = self
+            .advances
+            .get(&base)
+            .copied()
+            .unwrap_or(self.fallback * char_columns(base) as f32);
+        let mark_advance: f32 = chars.filter_map(|c| self.advances.get(&c)).sum();
+        (base_advance + mark_advance) * EM_SIZE
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(FontMetrics::load(PAPER_FONT_PATH));
+}
+
+// CJK and fullwidth glyphs are twice as wide as the monospace latin ones
+fn char_columns(c: char) -> i32 {
+    match c as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+// a grapheme in logical order before the line is reordered for display
+#[derive(Clone, Copy)]
+struct Cluster<'a> {
+    text: &'a str,
+    advance: f32,
+    to_redact: bool,
+}
+
+// Wraps a document into pages laid out by its template, text between <> is marked for redaction
+pub fn layout_pages(text: &str, metrics: &FontMetrics) -> Vec<PageLayout> {
+    let (template, argument, body) = Template::parse(text);
+    let mut writer = PageWriter::new(template, metrics, body);
+    if let (Template::Sheet | Template::Form, Some(argument)) = (template, argument) {
+        writer.rules = RedactionRule::parse_list(argument);
+    }
+    if let Some(rect) = template.photo_rect() {
+        // an image block at the start of a photo page is its print, otherwise the tag names it
+        if body.split(' ').find(|word| !word.is_empty()) == Some(IMAGE_OPEN) {
+            writer.print = Some(rect);
+        } else {
+            writer.page.photos.push(Photo {
+                rect,
+                image: argument.unwrap_or(DEFAULT_PHOTO).to_owned(),
+                regions: Vec::new(),
+            });
+        }
+        writer.y = rect.max.y + LINE_HEIGHT;
+    }
+    match template {
+        Template::Sheet => writer.write_sheet(body),
+        Template::Form => writer.write_form(body),
+        _ => writer.write_text(body),
+    }
+    writer.finish()
+}
+
+// words of a document grouped into the lines between `//` breaks
+fn split_lines(body: &str) -> Vec<Vec<&str>> {
+    body.split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .split(|word| *word == LINE_BREAK)
+        .filter(|words| !words.is_empty())
+        .map(<[&str]>::to_vec)
+        .collect()
+}
+
+// a cell or field as plain text, for matching against redaction rules
+fn cell_text(words: &[&str]) -> String {
+    words.join(" ").replace(['<', '>'], "")
+}
+
+// An image block as written in a document
+struct ImageSpec<'a> {
+    image: &'a str,
+    columns: f32,
+    lines: f32,
+    regions: Vec<Vec<Vec2>>,
+}
+
+impl<'a> ImageSpec<'a> {
+    // the words between `[image` and `]`
+    fn parse(words: &[&'a str]) -> Option<Self> {
+        let (&image, rest) = words.split_first()?;
+        let (&size, regions) = rest.split_first()?;
+        let (columns, lines) = size.split_once('x')?;
+        let regions = regions
+            .iter()
+            .map(|word| {
+                let (shape, numbers) = word.split_once(':')?;
+                let numbers: Vec<f32> = numbers
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .ok()?;
+                match (shape, numbers.as_slice()) {
+                    ("rect", &[x, y, width, height]) => Some(vec![
+                        Vec2::new(x, y),
+                        Vec2::new(x + width, y),
+                        Vec2::new(x + width, y + height),
+                        Vec2::new(x, y + height),
+                    ]),
+                    ("poly", corners) if corners.len() >= 6 && corners.len() % 2 == 0 => Some(
+                        corners
+                            .chunks(2)
+                            .map(|corner| Vec2::new(corner[0], corner[1]))
+                            .collect(),
+                    ),
+                    _ => None,
+                }
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            image,
+            columns: columns.parse().ok()?,
+            lines: lines.parse().ok()?,
+            regions,
+        })
+    }
+
+    // the print placed over `rect`, with its regions moved onto the page
+    fn photo(&self, rect: Rect) -> Photo {
+        Photo {
+            rect,
+            image: self.image.to_owned(),
+            regions: self
+                .regions
+                .iter()
+                .map(|corners| {
+                    corners
+                        .iter()
+                        .map(|&corner| rect.min + corner * rect.size())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+// Writes lines down the page in the template's style, starting new pages as they fill
+struct PageWriter<'a> {
+    metrics: &'a FontMetrics,
+    template: Template,
+    // the document reads in the direction of its first strong character
+    base_level: Level,
+    pages: Vec<PageLayout>,
+    page: PageLayout,
+    line: Vec<Cluster<'a>>,
+    width: f32,
+    // centre of the line being written
+    y: f32,
+    lines_written: usize,
+    style: LineStyle,
+    to_redact: bool,
+    // space kept for the print on a photo page, filled by its leading image block
+    print: Option<Rect>,
+    // sheet and form rules from the tag, and whether they cover the cell being written
+    rules: Vec<RedactionRule>,
+    force_redact: bool,
+}
+
+impl<'a> PageWriter<'a> {
+    fn new(template: Template, metrics: &'a FontMetrics, body: &str) -> Self {
+        Self {
+            metrics,
+            template,
+            base_level: ParagraphBidiInfo::new(body, None).paragraph_level,
+            pages: Vec::new(),
+            page: PageLayout::new(template),
+            line: Vec::new(),
+            width: 0.0,
+            y: 0.0,
+            lines_written: 0,
+            style: template.header_style(),
+            to_redact: false,
+            print: None,
+            rules: Vec::new(),
+            force_redact: false,
+        }
+    }
+
+    fn line_height(&self) -> f32 {
+        LINE_HEIGHT * self.style.scale * self.template.line_spacing()
+    }
+
+    // running text, `//` starts a new line, `---` rules one off, `[page]` starts a new page and
+    // `[image ... ]` embeds a print
+    fn write_text(&mut self, body: &'a str) {
+        let (left, line_width) = self.template.margins();
+        let mut words = body.split(' ').filter(|word| !word.is_empty());
+        while let Some(word) = words.next() {
+            match word {
+                IMAGE_OPEN => {
+                    let spec: Vec<&str> = words
+                        .by_ref()
+                        .take_while(|word| *word != IMAGE_CLOSE)
+                        .collect();
+                    match ImageSpec::parse(&spec) {
+                        Some(spec) => self.image(&spec),
+                        None => println!("Bad image block [image {}]", spec.join(" ")),
+                    }
+                }
+                LINE_BREAK => self.break_line(),
+                PAGE_BREAK => {
+                    if !self.line.is_empty() {
+                        self.break_line();
+                    }
+                    self.new_page();
+                }
+                RULE => {
+                    if !self.line.is_empty() {
+                        self.break_line();
+                    }
+                    self.rule(left, left + line_width, self.y);
+                    self.style = self.template.body_style();
+                    self.advance_line();
+                }
+                _ => self.push_word(word, line_width),
+            }
+        }
+    }
+
+    // one field per line, the words up to the first ending in ':' label it
+    fn write_form(&mut self, body: &'a str) {
+        let (left, line_width) = self.template.margins();
+        for words in split_lines(body) {
+            let label_end = words
+                .iter()
+                .position(|word| word.ends_with(':'))
+                .map_or(0, |index| index + 1);
+            let (label, value) = words.split_at(label_end);
+            let (label_text, value_text) = (cell_text(label), cell_text(value));
+            let force_redact = self
+                .rules
+                .iter()
+                .any(|rule| rule.matches(&label_text, &value_text));
+            if !label.is_empty() {
+                self.style = self.template.label_style();
+                for &word in label {
+                    self.push_word(word, line_width);
+                }
+                self.break_line();
+            }
+
+            self.style = self.template.body_style();
+            self.force_redact = force_redact;
+            for &word in value {
+                self.push_word(word, line_width);
+            }
+            self.force_redact = false;
+            let underline = self.y + self.line_height() * 0.45;
+            self.rule(left, left + line_width, underline);
+            self.break_line();
+        }
+    }
+
+    // rows of `|` separated cells ruled like a ledger, the first row heads the columns
+    fn write_sheet(&mut self, body: &'a str) {
+        let (left, table_width) = self.template.margins();
+        let lines = split_lines(body);
+
+        // lines above the first row of cells are the sheet's title
+        let title = lines
+            .iter()
+            .take_while(|words| !words.contains(&CELL_BREAK))
+            .count();
+        self.style = self.template.body_style();
+        for words in &lines[..title] {
+            for &word in words {
+                self.push_word(word, table_width);
+            }
+            self.break_line();
+        }
+
+        let rows: Vec<Vec<Vec<&str>>> = lines[title..]
+            .iter()
+            .map(|words| {
+                words
+                    .split(|word| *word == CELL_BREAK)
+                    .map(<[&str]>::to_vec)
+                    .collect()
+            })
+            .collect();
+        let Some((header, rows)) = rows.split_first() else {
+            return;
+        };
+        let headers: Vec<String> = header.iter().map(|words| cell_text(words)).collect();
+        let edges = self.column_edges(header, rows, left, table_width);
+        let right = left + table_width;
+
+        let row_height = self.line_height();
+        let mut top = self.y - row_height * 0.5;
+        self.rule(left, right, top);
+        self.style = self.template.header_style();
+        self.write_row(header, &[], &edges);
+        for cells in rows {
+            self.rule(left, right, self.y + row_height * 0.5);
+
+            // close off the table at the foot of the page and carry on under the header on the next
+            if self.y + row_height > PAGE_HEIGHT + FIT_TOLERANCE {
+                self.column_rules(&edges, top, self.y + row_height * 0.5);
+                self.advance_line();
+                top = self.y - row_height * 0.5;
+                self.rule(left, right, top);
+                self.style = self.template.header_style();
+                self.write_row(header, &[], &edges);
+                self.rule(left, right, self.y + row_height * 0.5);
+            }
+            self.style = self.template.body_style();
+            self.advance_line();
+            self.write_row(cells, &headers, &edges);
+        }
+        let bottom = self.y + row_height * 0.5;
+        self.rule(left, right, bottom);
+        self.column_rules(&edges, top, bottom);
+        self.style = self.template.body_style();
+        self.advance_line();
+    }
+
+    // columns share the table in proportion to their widest cell
+    fn column_edges(
+        &self,
+        header: &[Vec<&str>],
+        rows: &[Vec<Vec<&str>>],
+        left: f32,
+        table_width: f32,
+    ) -> Vec<f32> {
+        let scale = self.template.body_style().scale;
+        let columns = rows
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(header.len());
+        let mut widths = vec![CELL_WIDTH; columns];
+        for cells in std::iter::once(header).chain(rows.iter().map(Vec::as_slice)) {
+            for (column, words) in cells.iter().enumerate() {
+                let width: f32 = cell_text(words)
+                    .graphemes(true)
+                    .map(|grapheme| self.metrics.advance(grapheme) * scale)
+                    .sum();
+                widths[column] = widths[column].max(width + CELL_PADDING * 2.0);
+            }
+        }
+
+        let stretch = table_width / widths.iter().sum::<f32>();
+        let mut edges = vec![left];
+        for width in widths {
+            edges.push(edges[edges.len() - 1] + width * stretch);
+        }
+        edges
+    }
+
+    // one row of cells, `headers` is empty for the header row so no rules apply to it
+    fn write_row(&mut self, cells: &[Vec<&'a str>], headers: &[String], edges: &[f32]) {
+        for (column, words) in cells.iter().enumerate() {
+            let text = cell_text(words);
+            self.force_redact = headers
+                .get(column)
+                .is_some_and(|header| self.rules.iter().any(|rule| rule.matches(header, &text)));
+            let cell_width = edges[column + 1] - edges[column] - CELL_PADDING * 2.0;
+            let clusters = self.cell_clusters(words, cell_width);
+            self.place(&clusters, edges[column] + CELL_PADDING, cell_width);
+        }
+        self.force_redact = false;
+    }
+
+    fn push_word(&mut self, word: &'a str, line_width: f32) {
+        let clusters = self.clusters(word);
+        if clusters.is_empty() {
+            return;
+        }
+
+        let space_advance = self.metrics.advance(" ") * self.style.scale;
+        let word_width: f32 = clusters.iter().map(|cluster| cluster.advance).sum();
+        if !self.line.is_empty() {
+            if self.width + space_advance + word_width <= line_width + FIT_TOLERANCE {
+                self.line.push(Cluster {
+                    text: " ",
+                    advance: space_advance,
+                    to_redact: false,
+                });
+                self.width += space_advance;
+            } else {
+                self.break_line();
+            }
+        }
+
+        // words longer than a line, like unspaced CJK text, break between graphemes
+        for cluster in clusters {
+            if !self.line.is_empty() && self.width + cluster.advance > line_width + FIT_TOLERANCE {
+                self.break_line();
+            }
+            self.width += cluster.advance;
+            self.line.push(cluster);
+        }
+    }
+
+    // graphemes of a word in the current style, with the <>, stamp and shred markup taken out
+    fn clusters(&mut self, word: &'a str) -> Vec<Cluster<'a>> {
+        let mut clusters = Vec::new();
+        if word == SHRED_MARK {
+            self.page.must_shred = true;
+            return clusters;
+        }
+        if let Some(name) = word
+            .strip_prefix(STAMP_OPEN)
+            .and_then(|rest| rest.strip_suffix(IMAGE_CLOSE))
+        {
+            match StampKind::from_name(name) {
+                Some(kind) => self.page.required_stamps.push(kind),
+                None => println!("Unknown stamp {name}"),
+            }
+            return clusters;
+        }
+        for grapheme in word.graphemes(true) {
+            match grapheme {
+                "<" => self.to_redact = true,
+                ">" => self.to_redact = false,
+                _ => clusters.push(Cluster {
+                    text: grapheme,
+                    advance: self.metrics.advance(grapheme) * self.style.scale,
+                    to_redact: self.to_redact || self.force_redact,
+                }),
+            }
+        }
+        clusters
+    }
+
+    // a cell's words on one line, cut off at the cell edge
+    fn cell_clusters(&mut self, words: &[&'a str], cell_width: f32) -> Vec<Cluster<'a>> {
+        let space_advance = self.metrics.advance(" ") * self.style.scale;
+        let mut clusters = Vec::new();
+        let mut width = 0.0;
+        for &word in words {
+            let word_clusters = self.clusters(word);
+            if word_clusters.is_empty() {
+                continue;
+            }
+            if !clusters.is_empty() {
+                clusters.push(Cluster {
+                    text: " ",
+                    advance: space_advance,
+                    to_redact: false,
+                });
+                width += space_advance;
+            }
+            for cluster in word_clusters {
+                if width + cluster.advance <= cell_width + FIT_TOLERANCE {
+                    width += cluster.advance;
+                    clusters.push(cluster);
+                }
+            }
+        }
+        clusters
+    }
+
+    // places the current line between the margins and moves down to the next
+    fn break_line(&mut self) {
+        let (left, line_width) = self.template.margins();
+        let line = std::mem::take(&mut self.line);
+        self.place(&line, left, line_width);
+        self.width = 0.0;
+        self.advance_line();
+    }
+
+    fn advance_line(&mut self) {
+        self.y += self.line_height();
+        self.lines_written += 1;
+        if self.y > PAGE_HEIGHT + FIT_TOLERANCE {
+            self.new_page();
+        }
+    }
+
+    fn new_page(&mut self) {
+        let page = std::mem::replace(&mut self.page, PageLayout::new(self.template));
+        if !page.is_empty() {
+            self.pages.push(page);
+        }
+        self.y = 0.0;
+    }
+
+    // an embedded print on lines of its own, moved to the next page if it doesn't fit
+    fn image(&mut self, spec: &ImageSpec) {
+        if let Some(rect) = self.print.take() {
+            self.page.photos.push(spec.photo(rect));
+            return;
+        }
+        if !self.line.is_empty() {
+            self.break_line();
+        }
+
+        let (left, line_width) = self.template.margins();
+        let width = (spec.columns * CELL_WIDTH).min(line_width);
+        let height = spec.lines * LINE_HEIGHT;
+        let mut top = self.y - LINE_HEIGHT * 0.5;
+        if top + height > PAGE_HEIGHT + LINE_HEIGHT * 0.5 + FIT_TOLERANCE && !self.page.is_empty() {
+            self.new_page();
+            top = -LINE_HEIGHT * 0.5;
+        }
+        self.page
+            .photos
+            .push(spec.photo(Rect::new(left, top, left + width, top + height)));
+
+        self.y = top + height + self.line_height() * 0.5;
+        self.lines_written += spec.lines as usize;
+        if self.y > PAGE_HEIGHT + FIT_TOLERANCE {
+            self.new_page();
+        }
+    }
+
+    fn rule(&mut self, from_x: f32, to_x: f32, y: f32) {
+        self.page.rules.push(Rect::new(
+            from_x,
+            y - RULE_THICKNESS * 0.5,
+            to_x,
+            y + RULE_THICKNESS * 0.5,
+        ));
+    }
+
+    fn column_rules(&mut self, edges: &[f32], top: f32, bottom: f32) {
+        for &x in edges {
+            self.page.rules.push(Rect::new(
+                x - RULE_THICKNESS * 0.5,
+                top,
+                x + RULE_THICKNESS * 0.5,
+                bottom,
+            ));
+        }
+    }
+
+    fn finish(mut self) -> Vec<PageLayout> {
+        if !self.line.is_empty() {
+            self.break_line();
+        }
+        if !self.page.is_empty() {
+            self.pages.push(self.page);
+        }
+        self.pages
+    }
+
+    // reorders a line for display and gives each visible grapheme its position
+    fn place(&mut self, line: &[Cluster], left: f32, available: f32) {
+        if line.is_empty() {
+            return;
+        }
+        let mut text = String::new();
+        let mut starts = Vec::with_capacity(line.len());
+        for cluster in line {
+            starts.push(text.len());
+            text.push_str(cluster.text);
+        }
+        let line_width: f32 = line.iter().map(|cluster| cluster.advance).sum();
+
+        let info = ParagraphBidiInfo::new(&text, Some(self.base_level));
+        let (levels, runs) = info.visual_runs(0..text.len());
+
+        // right to left documents are aligned to the right margin
+        let line_x = if self.base_level.is_rtl() {
+            left + available - line_width
+        } else {
+            left
+        };
+        let placed = Line {
+            text: text.clone(),
+            x: line_x,
+            y: self.y,
+            width: line_width,
+            scale: self.style.scale,
+            ink: self.style.ink,
+            tilt: self.template.tilt(self.lines_written),
+        };
+
+        let mut x = line_x;
+        for run in runs {
+            let rtl = levels[run.start].is_rtl();
+            let mut indices: Vec<usize> = (0..line.len())
+                .filter(|&index| run.contains(&starts[index]))
+                .collect();
+            if rtl {
+                indices.reverse();
+            }
+            for index in indices {
+                let cluster = &line[index];
+                if !cluster.text.trim().is_empty() {
+                    let glyph = Glyph {
+                        text: if rtl {
+                            mirrored(cluster.text).to_owned()
+                        } else {
+                            cluster.text.to_owned()
+                        },
+                        x,
+                        // tilted lines drift down the page along their length
+                        y: self.y
+                            + (x + cluster.advance * 0.5 - placed.centre()) * placed.tilt.tan(),
+                        advance: cluster.advance,
+                        height: LINE_HEIGHT * self.style.scale,
+                        to_redact: cluster.to_redact,
+                    };
+                    self.page.grid.insert(self.page.glyphs.len(), &glyph);
+                    self.page.glyphs.push(glyph);
+                }
+                x += cluster.advance;
+            }
+        }
+        self.page.lines.push(placed);
+    }
+}
+
+// paired punctuation faces the other way in right to left runs
+fn mirrored(grapheme: &str) -> &str {
+    match grapheme {
+        "(" => ")",
+        ")" => "(",
+        "[" => "]",
+        "]" => "[",
+        "{" => "}",
+        "}" => "{",
+        "«" => "»",
+        "»" => "«",
+        _ => grapheme,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one monospace column, the fallback advance and every advance in the paper font
+    const COLUMN: f32 = DEFAULT_ADVANCE * EM_SIZE;
+
+    // fixed advances, so the tests don't depend on the font file
+    fn layout(text: &str) -> Vec<PageLayout> {
+        layout_pages(text, &FontMetrics::default())
+    }
+
+    // glyphs drawn on a line, in visual order
+    fn line_glyphs<'a>(page: &'a PageLayout, line: &Line) -> Vec<&'a str> {
+        page.glyphs
+            .iter()
+            .filter(|glyph| (glyph.y - line.y).abs() < 1e-5)
+            .map(|glyph| glyph.text.as_str())
+            .collect()
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn wraps_accented_latin_at_the_line_width() {
+        let pages = layout("résumé café naïve façade crème brûlée");
+        let page = &pages[0];
+        let lines: Vec<&str> = page.lines.iter().map(|line| line.text.as_str()).collect();
+        // the first line is exactly LINE_WIDTH wide, 24 columns
+        assert_eq!(lines, ["résumé café naïve façade", "crème brûlée"]);
+        assert_close(page.lines[0].width, LINE_WIDTH);
+        assert_eq!(line_glyphs(page, &page.lines[0]).len(), 21);
+        assert_eq!(line_glyphs(page, &page.lines[1]).len(), 11);
+    }
+
+    #[test]
+    fn combining_marks_stay_on_their_base_glyph() {
+        let pages = layout("cafe\u{301} noir");
+        let page = &pages[0];
+        let glyphs = line_glyphs(page, &page.lines[0]);
+        assert_eq!(glyphs, ["c", "a", "f", "e\u{301}", "n", "o", "i", "r"]);
+        assert_close(page.lines[0].width, 9.0 * COLUMN);
+        // the mark takes no column of its own, so the space still starts at the fifth column
+        assert_close(page.glyphs[4].x, 5.0 * COLUMN);
+    }
+
+    #[test]
+    fn cjk_glyphs_take_two_columns_and_break_between_graphemes() {
+        let pages = layout("日本語の文書を黒く塗りつぶす作業です");
+        let page = &pages[0];
+        let lines: Vec<&str> = page.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(lines, ["日本語の文書を黒く塗りつ", "ぶす作業です"]);
+        assert_eq!(line_glyphs(page, &page.lines[0]).len(), 12);
+        assert_eq!(line_glyphs(page, &page.lines[1]).len(), 6);
+        assert_close(page.glyphs[0].advance, 2.0 * COLUMN);
+        assert_close(page.glyphs[1].x, 2.0 * COLUMN);
+    }
+
+    #[test]
+    fn rtl_lines_are_right_aligned_with_mirrored_brackets() {
+        let pages = layout("שלום (עולם)");
+        let page = &pages[0];
+        let line = &page.lines[0];
+        assert_close(line.width, 11.0 * COLUMN);
+        assert_close(line.x, LINE_WIDTH - line.width);
+
+        // read left to right on the page, the closing bracket comes first and faces the text
+        let glyphs = line_glyphs(page, line);
+        assert_eq!(glyphs, ["(", "ם", "ל", "ו", "ע", ")", "ם", "ו", "ל", "ש"]);
+        assert_close(page.glyphs[0].x, line.x);
+        let last = &page.glyphs[page.glyphs.len() - 1];
+        assert_close(last.x + last.advance, LINE_WIDTH);
+    }
+
+    #[test]
+    fn angle_brackets_mark_only_the_glyphs_inside() {
+        let pages = layout("Chez <He\u{301}le\u{300}ne> ce soir, <Ann>.");
+        let page = &pages[0];
+        let redacted: Vec<&str> = page
+            .glyphs
+            .iter()
+            .filter(|glyph| glyph.to_redact)
+            .map(|glyph| glyph.text.as_str())
+            .collect();
+        let kept: String = page
+            .glyphs
+            .iter()
+            .filter(|glyph| !glyph.to_redact)
+            .map(|glyph| glyph.text.as_str())
+            .collect();
+        assert_eq!(
+            redacted,
+            ["H", "e\u{301}", "l", "e\u{300}", "n", "e", "A", "n", "n"]
+        );
+        assert_eq!(kept, "Chezcesoir,.");
+        // the brackets themselves are never drawn
+        assert!(
+            page.glyphs
+                .iter()
+                .all(|glyph| glyph.text != "<" && glyph.text != ">")
+        );
+    }
+
+    #[test]
+    fn paper_font_advances_match_its_monospace_columns() {
+        // tests run from the crate root, where the assets are
+        let metrics = FontMetrics::load(PAPER_FONT_PATH);
+        assert!(metrics.advances.contains_key(&'é'));
+        for grapheme in ["a", "W", "é", " ", "<"] {
+            assert_close(metrics.advance(grapheme), COLUMN);
+        }
+
+        let pages = layout_pages("résumé café naïve façade crème brûlée", &metrics);
+        let page = &pages[0];
+        let lines: Vec<&str> = page.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(lines, ["résumé café naïve façade", "crème brûlée"]);
+        // the c of café starts after "résumé" and a space
+        assert_eq!(page.glyphs[6].text, "c");
+        assert_close(page.glyphs[6].x, 7.0 * COLUMN);
+    }
+
+    #[test]
+    fn proportional_advances_move_the_wrap_point_and_the_glyphs() {
+        let metrics = FontMetrics {
+            advances: HashMap::from_iter([('m', 0.9), ('i', 0.25), (' ', 0.3)]),
+            fallback: DEFAULT_ADVANCE,
+        };
+        let text = "mmmmmmmm iiii mmmmmmmm";
+        // 22 fixed columns fit on one line
+        assert_eq!(layout(text)[0].lines.len(), 1);
+
+        // 7.2 + 0.3 + 1.0 + 0.3 + 7.2 ems is past the 14.688 em line, so the last word wraps
+        let pages = layout_pages(text, &metrics);
+        let page = &pages[0];
+        let lines: Vec<&str> = page.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(lines, ["mmmmmmmm iiii", "mmmmmmmm"]);
+        assert_close(page.lines[0].width, 8.5 * EM_SIZE);
+
+        let first = line_glyphs(page, &page.lines[0]);
+        assert_eq!(first.len(), 12);
+        for (index, glyph) in page.glyphs[..8].iter().enumerate() {
+            assert_close(glyph.x, index as f32 * 0.9 * EM_SIZE);
+            assert_close(glyph.advance, 0.9 * EM_SIZE);
+        }
+        for (index, glyph) in page.glyphs[8..12].iter().enumerate() {
+            assert_close(glyph.x, (7.5 + index as f32 * 0.25) * EM_SIZE);
+            assert_close(glyph.advance, 0.25 * EM_SIZE);
+        }
+        // the wrapped word starts back at the margin
+        assert_close(page.glyphs[12].x, 0.0);
+        assert_close(page.glyphs[19].x, 7.0 * 0.9 * EM_SIZE);
+    }
+}
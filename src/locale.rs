@@ -0,0 +1,150 @@
+use std::{fs, path::Path};
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::settings::Settings;
+
+const LOCALE_DIR: &str = "assets/locale";
+const TEXT_DIR: &str = "assets/text";
+// covers accented latin, the default UI font is ascii only
+pub const UI_FONT_PATH: &str = "fonts/SpaceMono-Regular.ttf";
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::French, Language::German];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+            Language::German => "de",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    // always shown in the language itself
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// String table for the selected language, falling back to English for missing keys
+#[derive(Resource)]
+pub struct Locale {
+    pub language: Language,
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Locale {
+    pub fn load(language: Language) -> Self {
+        Self {
+            language,
+            strings: load_strings(language),
+            fallback: load_strings(Language::English),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_owned())
+    }
+
+    // fills `{ $name }` placeables from args
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        let message = self.get(key);
+        let mut text = String::new();
+        let mut rest = message.as_str();
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            let name = rest[start + 1..start + end].trim().trim_start_matches('$');
+            match args.iter().find(|(arg, _)| *arg == name) {
+                Some((_, value)) => text.push_str(value),
+                None => text.push_str(&rest[start..start + end + 1]),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        text.push_str(rest);
+        text
+    }
+
+    // the translated copy of a document if the language pack has one
+    pub fn document_path(&self, path: &str) -> String {
+        if let Some(relative) = path.strip_prefix(TEXT_DIR) {
+            let localized = format!("{TEXT_DIR}/{}{relative}", self.language.code());
+            if Path::new(&localized).exists() {
+                return localized;
+            }
+        }
+        path.to_owned()
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Locale::load(Language::default()))
+        .add_systems(PreUpdate, update_language);
+}
+
+// parses the `key = value` subset of Fluent, indented lines continue the previous value
+fn load_strings(language: Language) -> HashMap<String, String> {
+    let mut strings = HashMap::new();
+    let path = format!("{LOCALE_DIR}/{}.ftl", language.code());
+    let Ok(contents) = fs::read_to_string(&path) else {
+        println!("Missing string table {path}");
+        return strings;
+    };
+
+    let mut last_key: Option<String> = None;
+    for line in contents.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            if let Some(value) = last_key.as_ref().and_then(|key| strings.get_mut(key)) {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_owned();
+            strings.insert(key.clone(), value.trim().to_owned());
+            last_key = Some(key);
+        }
+    }
+    strings
+}
+
+fn update_language(settings: Res<Settings>, mut locale: ResMut<Locale>) {
+    if settings.is_changed() && settings.language != locale.language {
+        *locale = Locale::load(settings.language);
+    }
+}
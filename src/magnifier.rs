@@ -0,0 +1,94 @@
+use bevy::{camera::Viewport, prelude::*};
+
+use crate::{
+    GameState,
+    camera::MainCamera,
+    paper::PAPER_POS,
+    pen::{Marker, tip_spot},
+};
+
+const MAGNIFIER_KEY: KeyCode = KeyCode::KeyM;
+const MAGNIFICATION: f32 = 3.0;
+// size of the lens as a fraction of the screen height
+const LENS_SIZE: f32 = 0.3;
+// the lens sits above and to the side of the marker so it doesn't cover the tip
+const LENS_OFFSET: Vec2 = Vec2::new(0.12, -0.22);
+
+// A second camera drawn in a small viewport, looking closely at the page under the marker
+#[derive(Component)]
+struct Magnifier;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (toggle_magnifier, follow_marker)
+                .chain()
+                .run_if(in_state(GameState::PLAYING)),
+        );
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            // drawn over the main view
+            order: 1,
+            is_active: false,
+            ..default()
+        },
+        Magnifier,
+        DespawnOnExit(GameState::PLAYING),
+    ));
+}
+
+fn toggle_magnifier(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut lens: Single<&mut Camera, With<Magnifier>>,
+) {
+    if keyboard_input.just_pressed(MAGNIFIER_KEY) {
+        lens.is_active = !lens.is_active;
+    }
+}
+
+// looks from the chair at the spot the marker would touch, and draws that beside the marker
+fn follow_marker(
+    marker: Single<&Transform, (With<Marker>, Without<Magnifier>)>,
+    main: Single<(&Camera, &GlobalTransform, &Projection), (With<MainCamera>, Without<Magnifier>)>,
+    lens: Single<(&mut Camera, &mut Transform, &mut Projection), With<Magnifier>>,
+) {
+    let (mut lens_camera, mut lens_transform, mut lens_projection) = lens.into_inner();
+    if !lens_camera.is_active {
+        return;
+    }
+    let (main_camera, main_transform, main_projection) = main.into_inner();
+
+    // where the marker's ray meets the page
+    let spot = tip_spot(&marker, PAPER_POS.y);
+
+    let (Some(screen), Some(ndc)) = (
+        main_camera.physical_viewport_size(),
+        main_camera.world_to_ndc(main_transform, spot),
+    ) else {
+        return;
+    };
+    let screen = screen.as_vec2();
+    let size = (screen.y * LENS_SIZE).floor();
+    // ndc runs bottom to top, the viewport top to bottom
+    let centre = Vec2::new((ndc.x + 1.0) * 0.5, (1.0 - ndc.y) * 0.5) + LENS_OFFSET;
+    let corner = (centre * screen - size * 0.5).clamp(Vec2::ZERO, screen - size);
+    lens_camera.viewport = Some(Viewport {
+        physical_position: corner.as_uvec2(),
+        physical_size: UVec2::splat(size as u32),
+        ..default()
+    });
+
+    *lens_transform =
+        Transform::from_translation(main_transform.translation()).looking_at(spot, Vec3::Y);
+    if let (Projection::Perspective(main_perspective), Projection::Perspective(lens_perspective)) =
+        (main_projection, &mut *lens_projection)
+    {
+        // the lens covers LENS_SIZE of the screen's height, so it needs that share of the view
+        lens_perspective.fov = main_perspective.fov * LENS_SIZE / MAGNIFICATION;
+    }
+}
@@ -9,27 +9,47 @@ use bevy::{
     },
 };
 
+use std::time::Duration;
+
 mod audio;
+mod camera;
+mod campaign;
+mod cap;
 mod clock;
 mod end;
 mod environment;
 mod feedback;
+mod hud;
+mod layout;
 mod loading;
+mod locale;
+mod magnifier;
 mod menu;
 mod mob;
+mod music;
 mod paint;
 mod paper;
 mod pen;
 mod planner;
+mod procgen;
+mod settings;
+mod shredder;
+mod stack;
+mod stamp;
+mod table;
+mod template;
 mod text_asset;
+mod tutorial;
 
 pub const LIFETIME: f32 = 60.0;
+pub const TIME_BONUS_PER_SECOND: u32 = 10;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
 pub enum GameState {
     #[default]
     LOADING,
     MENU,
+    SETTINGS,
     PLAYING,
     END,
 }
@@ -37,6 +57,72 @@ pub enum GameState {
 #[derive(Resource)]
 pub struct CountdownTimer(Timer);
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum ShiftOutcome {
+    // the mob broke in before every page was processed, pages were filed with targets still
+    // showing or without their stamps, or the wrong pages went into the shredder
+    #[default]
+    Leaked,
+    // every page was processed cleanly before the timer ran out
+    Completed,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum GameMode {
+    // a single shift on the bee movie script
+    #[default]
+    Shift,
+    Campaign,
+    // procedurally generated pages until the mob breaks in
+    Endless,
+    // a guided practice shift, the boss walks the player through each action
+    Tutorial,
+}
+
+// Everything the next shift is played with, set by the menus before entering PLAYING
+#[derive(Resource)]
+pub struct ActiveShift {
+    pub mode: GameMode,
+    pub document: String,
+    pub directive: String,
+    pub lifetime: f32,
+    pub unlocks: Vec<campaign::Unlock>,
+}
+
+impl Default for ActiveShift {
+    fn default() -> Self {
+        Self {
+            mode: GameMode::Shift,
+            document: "assets/text/beemovie.txt".to_owned(),
+            directive: "Redact anything related to Bees NOW".to_owned(),
+            lifetime: LIFETIME,
+            unlocks: Vec::new(),
+        }
+    }
+}
+
+impl ActiveShift {
+    // the quick shift from the main menu, its script has no translated pack and is read in
+    // English whatever the language
+    pub fn shift(locale: &locale::Locale) -> Self {
+        Self {
+            directive: locale.get("directive-bees"),
+            ..default()
+        }
+    }
+
+    pub fn has_unlock(&self, unlock: campaign::Unlock) -> bool {
+        self.unlocks.contains(&unlock)
+    }
+}
+
+// How the last shift ended, read by the end screen
+#[derive(Resource, Default)]
+pub struct ShiftResult {
+    pub outcome: ShiftOutcome,
+    pub time_bonus: u32,
+}
+
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
@@ -65,6 +151,8 @@ fn main() {
             LIFETIME,
             TimerMode::Once,
         )))
+        .insert_resource(ShiftResult::default())
+        .insert_resource(ActiveShift::default())
         .add_plugins(MeshPickingPlugin)
         .init_state::<GameState>()
         .add_systems(
@@ -77,12 +165,26 @@ fn main() {
         .add_plugins(end::plugin)
         .add_plugins(paper::plugin)
         .add_plugins(mob::plugin)
+        .add_plugins(music::plugin)
         .add_plugins(pen::plugin)
+        .add_plugins(cap::plugin)
         .add_plugins(clock::plugin)
         .add_plugins(environment::plugin)
+        .add_plugins(camera::plugin)
+        .add_plugins(magnifier::plugin)
         .add_plugins(paint::plugin)
         .add_plugins(feedback::plugin)
         .add_plugins(planner::plugin)
+        .add_plugins(hud::plugin)
+        .add_plugins(campaign::plugin)
+        .add_plugins(procgen::plugin)
+        .add_plugins(settings::plugin)
+        .add_plugins(stack::plugin)
+        .add_plugins(stamp::plugin)
+        .add_plugins(shredder::plugin)
+        .add_plugins(layout::plugin)
+        .add_plugins(locale::plugin)
+        .add_plugins(tutorial::plugin)
         // .add_systems(Update, framerate)
         .run();
 }
@@ -95,8 +197,18 @@ fn update_countdown(
     mut next_state: ResMut<NextState<GameState>>,
     time: Res<Time>,
     mut timer: ResMut<CountdownTimer>,
+    mut shift_result: ResMut<ShiftResult>,
+    settings: Res<settings::Settings>,
 ) {
-    if timer.0.tick(time.delta()).is_finished() {
+    // with page timers the shift clock only moves on penalties
+    let delta = if settings.page_timers {
+        Duration::ZERO
+    } else {
+        time.delta()
+    };
+    if timer.0.tick(delta).is_finished() {
+        shift_result.outcome = ShiftOutcome::Leaked;
+        shift_result.time_bonus = 0;
         next_state.set(GameState::END);
     }
 }
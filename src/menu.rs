@@ -1,10 +1,23 @@
 use bevy::{app::AppExit, prelude::*};
 
+use crate::{
+    ActiveShift,
+    campaign::{CampaignProgress, save_progress},
+    locale::{Locale, UI_FONT_PATH},
+    procgen::EndlessRun,
+    tutorial,
+};
+
 use super::GameState;
 
 #[derive(Component)]
 enum MenuAction {
     Play,
+    Tutorial,
+    Continue,
+    NewCampaign,
+    Endless,
+    Settings,
     Quit,
 }
 
@@ -34,14 +47,20 @@ pub(super) fn plugin(app: &mut App) {
         );
 }
 
-fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    progress: Res<CampaignProgress>,
+    locale: Res<Locale>,
+) {
     commands.spawn((DespawnOnExit(GameState::MENU), Camera2d));
 
     // Common style for all buttons on the screen
     let button_node = Node {
         width: px(300),
-        height: px(65),
-        margin: UiRect::all(px(20)),
+        // short enough for every button to fit on a 720p window
+        height: px(60),
+        margin: UiRect::all(px(6)),
         justify_content: JustifyContent::Center,
         align_items: AlignItems::Center,
         ..default()
@@ -54,7 +73,9 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         left: px(10),
         ..default()
     };
+    let ui_font: Handle<Font> = asset_server.load(UI_FONT_PATH);
     let button_text_font = TextFont {
+        font: ui_font.clone(),
         font_size: 33.0,
         ..default()
     };
@@ -64,65 +85,87 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let exit_icon = asset_server.load("menu/exit.png");
     let background_image = asset_server.load("menu/main.png");
 
-    commands.spawn((
-        DespawnOnExit(GameState::MENU),
-        Node {
-            width: percent(100),
-            height: percent(100),
+    let column = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
             align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
             ..default()
-        },
-        ImageNode::new(background_image.clone()),
-        Menu,
-        children![(
-            Node {
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            children![
-                // Display the game name
-                (
-                    Text::new("REDACTED"),
-                    TextFont {
-                        font_size: 128.0,
-                        ..default()
-                    },
-                    TextColor(TITLE_COLOR),
-                    Node {
-                        margin: UiRect::all(px(50)),
-                        ..default()
-                    },
-                ),
-                // Display Buttons
-                (
+        })
+        .with_children(|parent| {
+            // Display the game name
+            parent.spawn((
+                Text::new(locale.get("menu-title")),
+                TextFont {
+                    font: ui_font,
+                    font_size: 128.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::all(px(20)),
+                    ..default()
+                },
+            ));
+
+            // Display Buttons
+            let mut buttons = vec![
+                (MenuAction::Play, "button-play"),
+                (MenuAction::Tutorial, "button-tutorial"),
+            ];
+            if progress.can_continue() {
+                buttons.push((MenuAction::Continue, "button-continue"));
+            }
+            buttons.push((MenuAction::NewCampaign, "button-new-campaign"));
+            buttons.push((MenuAction::Endless, "button-endless"));
+            buttons.push((MenuAction::Settings, "button-settings"));
+
+            for (action, label) in buttons {
+                parent.spawn((
                     Button,
                     button_node.clone(),
                     BackgroundColor(NORMAL_BUTTON),
-                    MenuAction::Play,
+                    action,
                     children![
-                        (ImageNode::new(right_icon), button_icon_node.clone()),
+                        (ImageNode::new(right_icon.clone()), button_icon_node.clone()),
                         (
-                            Text::new("Play"),
+                            Text::new(locale.get(label)),
                             button_text_font.clone(),
                             TextColor(TEXT_COLOR),
                         ),
-                    ]
-                ),
-                (
-                    Button,
-                    button_node,
-                    BackgroundColor(NORMAL_BUTTON),
-                    MenuAction::Quit,
-                    children![
-                        (ImageNode::new(exit_icon), button_icon_node),
-                        (Text::new("Quit"), button_text_font, TextColor(TEXT_COLOR),),
-                    ]
-                ),
-            ]
-        )],
-    ));
+                    ],
+                ));
+            }
+            parent.spawn((
+                Button,
+                button_node,
+                BackgroundColor(NORMAL_BUTTON),
+                MenuAction::Quit,
+                children![
+                    (ImageNode::new(exit_icon), button_icon_node),
+                    (
+                        Text::new(locale.get("button-quit")),
+                        button_text_font,
+                        TextColor(TEXT_COLOR),
+                    ),
+                ],
+            ));
+        })
+        .id();
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::MENU),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ImageNode::new(background_image.clone()),
+            Menu,
+        ))
+        .add_child(column);
 }
 
 // This system handles changing all buttons color based on mouse interaction
@@ -147,6 +190,10 @@ fn menu_action(
     interaction_query: Query<(&Interaction, &MenuAction), (Changed<Interaction>, With<Button>)>,
     mut app_exit_writer: MessageWriter<AppExit>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut active_shift: ResMut<ActiveShift>,
+    mut progress: ResMut<CampaignProgress>,
+    mut endless_run: ResMut<EndlessRun>,
+    locale: Res<Locale>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -157,8 +204,31 @@ fn menu_action(
                     //app_exit_writer.write(AppExit::Success);
                 }
                 MenuAction::Play => {
+                    *active_shift = ActiveShift::shift(&locale);
                     game_state.set(GameState::PLAYING);
                 }
+                MenuAction::Tutorial => {
+                    *active_shift = tutorial::active_shift(&locale);
+                    game_state.set(GameState::PLAYING);
+                }
+                MenuAction::Continue => {
+                    *active_shift = progress.active_shift(&locale);
+                    game_state.set(GameState::PLAYING);
+                }
+                MenuAction::NewCampaign => {
+                    *progress = CampaignProgress::default();
+                    save_progress(&progress);
+                    *active_shift = progress.active_shift(&locale);
+                    game_state.set(GameState::PLAYING);
+                }
+                MenuAction::Endless => {
+                    *endless_run = EndlessRun::default();
+                    *active_shift = endless_run.active_shift(&locale);
+                    game_state.set(GameState::PLAYING);
+                }
+                MenuAction::Settings => {
+                    game_state.set(GameState::SETTINGS);
+                }
             }
         }
     }
@@ -5,27 +5,67 @@ use bevy::prelude::*;
 use bevy_sprite3d::Sprite3d;
 use rand::Rng;
 
+use crate::audio::{Bus, Emitter, LoopHandle, SoundBank, Sounds, start_loop};
 use crate::environment::{GlassCrackStage, PIXELS_PER_METRE};
 use crate::loading::GameAssets;
-use crate::{CountdownTimer, GameState, LIFETIME};
+use crate::{CountdownTimer, GameState};
 
 pub const MAX_MOB_MEMBERS: u32 = 32;
 pub const MOB_ATTACK_ADVANCE: f32 = 10.0;
 pub const GLASS_BREAK_STAGE: usize = 9;
+// the crowd is heard through this many loops, each placed among a slice of the mob
+const MOB_VOICES: usize = 4;
+// where the voices wait outside the window until there's a mob to stand among
+const MOB_VOICE_SPREAD: f32 = 15.0;
+const MOB_VOICE_Z: f32 = 12.0;
+
+// The crowd outside, louder with every crack in the glass
+#[derive(Resource)]
+pub struct MobAmbience(pub Vec<LoopHandle>);
+
+// Carries one of the mob's loops, moved to the middle of its part of the crowd
+#[derive(Component)]
+struct MobVoice(usize);
 
 #[derive(Component)]
-struct MobMember {
+pub struct MobMember {
     anger: f32,
     offset: f32,
     z: f32,
 }
 
 pub(super) fn plugin(app: &mut App) {
-    // app.add_systems(OnEnter(GameState::PLAYING), setup)
-    app.add_systems(Update, update_mob.run_if(in_state(GameState::PLAYING)));
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (update_mob, place_voices).run_if(in_state(GameState::PLAYING)),
+        );
 }
 
-// fn setup(mut commands: Commands, assets: Res<GameAssets>) {}
+// the mob starts out silent and grows with the cracks
+fn setup(mut commands: Commands, sound_bank: Res<SoundBank>) {
+    let mut handles = Vec::new();
+    for index in 0..MOB_VOICES {
+        let x = (index as f32 / (MOB_VOICES - 1) as f32 - 0.5) * MOB_VOICE_SPREAD;
+        let voice = commands
+            .spawn((
+                MobVoice(index),
+                Transform::from_xyz(x, 0.5, MOB_VOICE_Z),
+                DespawnOnExit(GameState::PLAYING),
+            ))
+            .id();
+        handles.push(start_loop(
+            &mut commands,
+            &sound_bank,
+            Sounds::Mob,
+            Bus::Ambience,
+            Emitter::Follow(voice),
+            0.0,
+            0.0,
+        ));
+    }
+    commands.insert_resource(MobAmbience(handles));
+}
 
 fn update_mob(
     countdown: Res<CountdownTimer>,
@@ -34,13 +74,14 @@ fn update_mob(
     assets: Res<GameAssets>,
     glass_crack_stage: ResMut<GlassCrackStage>,
 ) {
-    let progress = 1.0 - countdown.0.remaining().as_secs_f32() / LIFETIME;
+    let lifetime = countdown.0.duration().as_secs_f32();
+    let progress = 1.0 - countdown.0.remaining().as_secs_f32() / lifetime;
     let target_number_of_mob_members = floor(progress * MAX_MOB_MEMBERS as f32) as usize;
     let mob_members = members.count();
     if mob_members < target_number_of_mob_members {
         spawn_mob(&mut commands, &assets);
     }
-    let mob_attack_duration = LIFETIME * (1.0 - (GLASS_BREAK_STAGE as f32) / 11.0);
+    let mob_attack_duration = lifetime * (1.0 - (GLASS_BREAK_STAGE as f32) / 11.0);
     let mob_attack_progress = 1.0 - (countdown.0.remaining_secs() / mob_attack_duration);
     // println!(
     //     "glass_crack_stage: {} mob_attack_progress: {}",
@@ -78,3 +119,22 @@ fn spawn_mob(commands: &mut Commands, assets: &Res<GameAssets>) {
         DespawnOnExit(GameState::PLAYING),
     ));
 }
+
+// splits the mob from left to right and puts each voice in the middle of its share
+fn place_voices(
+    members: Query<&Transform, With<MobMember>>,
+    mut voices: Query<(&MobVoice, &mut Transform), Without<MobMember>>,
+) {
+    let mut positions: Vec<Vec3> = members.iter().map(|member| member.translation).collect();
+    if positions.is_empty() {
+        return;
+    }
+    positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+    let share = positions.len().div_ceil(MOB_VOICES);
+    for (voice, mut transform) in &mut voices {
+        let start = (voice.0 * share).min(positions.len() - 1);
+        let end = (start + share).min(positions.len());
+        let slice = &positions[start..end];
+        transform.translation = slice.iter().sum::<Vec3>() / slice.len() as f32;
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::{
+    CountdownTimer, GameState,
+    audio::{
+        Bus, Emitter, LoopCommand, LoopEvent, LoopHandle, SoundBank, SoundEvent, Sounds, start_loop,
+    },
+    environment::{GLASS_SHATTER_STAGE, GlassCrackStage},
+    mob::{MAX_MOB_MEMBERS, MobMember},
+    paper::PageScores,
+    stack::PageFiledEvent,
+};
+
+// stems in the order they join, with the tension each one starts fading in at, the drone
+// plays from the start
+const STEMS: [(Sounds, f32); 4] = [
+    (Sounds::MusicDrone, f32::NEG_INFINITY),
+    (Sounds::MusicPulse, 0.2),
+    (Sounds::MusicTicks, 0.4),
+    (Sounds::MusicStrings, 0.6),
+];
+// how much tension a stem needs past its threshold to play at full volume
+const STEM_RAMP: f32 = 0.15;
+const STEM_FADE_SECONDS: f32 = 1.5;
+// stems are only refaded once their volume has moved this far, not every frame
+const STEM_VOLUME_STEP: f32 = 0.05;
+// how much each part of the shift counts towards the tension
+const TIME_WEIGHT: f32 = 0.45;
+const MOB_WEIGHT: f32 = 0.3;
+const LEAK_WEIGHT: f32 = 0.25;
+// leaked characters at which the leak part is about two thirds of the way up
+const LEAK_SCALE: f32 = 20.0;
+// how quickly the tension follows the state of the shift, per second
+const TENSION_SPEED: f32 = 0.2;
+
+// How close the shift is to going wrong, from 0 when calm to 1 at the breach
+#[derive(Resource, Default)]
+pub struct Tension(pub f32);
+
+// One playing stem and the volume it was last faded to
+struct Stem {
+    handle: LoopHandle,
+    threshold: f32,
+    volume: f32,
+}
+
+#[derive(Resource)]
+struct Music {
+    stems: Vec<Stem>,
+    breached: bool,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Tension>()
+        .add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (update_tension, mix_stems, breach_sting)
+                .chain()
+                .run_if(in_state(GameState::PLAYING)),
+        )
+        .add_observer(page_sting);
+}
+
+// every stem starts together so they stay in step, silent until the tension calls for them
+fn setup(mut commands: Commands, sound_bank: Res<SoundBank>, mut tension: ResMut<Tension>) {
+    tension.0 = 0.0;
+    let stems = STEMS
+        .into_iter()
+        .map(|(sound, threshold)| Stem {
+            handle: start_loop(
+                &mut commands,
+                &sound_bank,
+                sound,
+                Bus::Music,
+                Emitter::Flat,
+                0.0,
+                0.0,
+            ),
+            threshold,
+            volume: 0.0,
+        })
+        .collect();
+    commands.insert_resource(Music {
+        stems,
+        breached: false,
+    });
+}
+
+// the clock running down, the mob gathering and leaked characters all wind it up
+fn update_tension(
+    time: Res<Time>,
+    countdown: Res<CountdownTimer>,
+    page_scores: Res<PageScores>,
+    members: Query<(), With<MobMember>>,
+    mut tension: ResMut<Tension>,
+) {
+    let clock = countdown.0.fraction();
+    let mob = members.iter().count() as f32 / MAX_MOB_MEMBERS as f32;
+    let leaks = 1.0 - (-(page_scores.leaked as f32) / LEAK_SCALE).exp();
+    let target =
+        (clock * TIME_WEIGHT + mob.min(1.0) * MOB_WEIGHT + leaks * LEAK_WEIGHT).clamp(0.0, 1.0);
+
+    let step = TENSION_SPEED * time.delta_secs();
+    tension.0 += (target - tension.0).clamp(-step, step);
+}
+
+fn mix_stems(mut commands: Commands, tension: Res<Tension>, mut music: ResMut<Music>) {
+    for stem in &mut music.stems {
+        let volume = ((tension.0 - stem.threshold) / STEM_RAMP).clamp(0.0, 1.0);
+        // always finish the fade all the way in or out
+        let settled = volume == 0.0 || volume == 1.0;
+        if volume == stem.volume || (!settled && (volume - stem.volume).abs() < STEM_VOLUME_STEP) {
+            continue;
+        }
+        stem.volume = volume;
+        commands.trigger(LoopEvent {
+            handle: stem.handle,
+            command: LoopCommand::Fade {
+                volume,
+                seconds: STEM_FADE_SECONDS,
+            },
+        });
+    }
+}
+
+fn breach_sting(
+    mut commands: Commands,
+    glass_crack_stage: Res<GlassCrackStage>,
+    mut music: ResMut<Music>,
+) {
+    if music.breached || glass_crack_stage.0 < GLASS_SHATTER_STAGE {
+        return;
+    }
+    music.breached = true;
+    commands.trigger(SoundEvent {
+        sound: Sounds::StingBreach,
+        bus: Bus::Music,
+        emitter: Emitter::Flat,
+        setting: PlaybackSettings::DESPAWN,
+    });
+}
+
+fn page_sting(_filed: On<PageFiledEvent>, mut commands: Commands) {
+    commands.trigger(SoundEvent {
+        sound: Sounds::StingPage,
+        bus: Bus::Music,
+        emitter: Emitter::Flat,
+        setting: PlaybackSettings::DESPAWN,
+    });
+}
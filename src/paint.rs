@@ -1,16 +1,91 @@
 use bevy::camera::RenderTarget;
 use bevy::{camera::visibility::RenderLayers, prelude::*, render::render_resource::TextureFormat};
 
-use crate::GameState;
 use crate::audio::{SoundEvent, Sounds};
+use crate::campaign::Unlock;
 use crate::paper::PAPER_POS;
 use crate::pen::{InkSupplyPercent, Marker};
+use crate::{ActiveShift, GameState};
 
 #[derive(Resource, Default)]
 struct BrushState {
     last_pos: Option<Vec2>,
 }
 
+// Where ink has been laid on the canvas, kept on the CPU so inked areas can be scored
+#[derive(Resource)]
+pub struct InkCoverage {
+    inked: Vec<bool>,
+}
+
+impl Default for InkCoverage {
+    fn default() -> Self {
+        Self {
+            inked: vec![false; (COVERAGE_COLUMNS * COVERAGE_ROWS) as usize],
+        }
+    }
+}
+
+impl InkCoverage {
+    // fraction of the polygon on the paper with ink over it
+    pub fn coverage(&self, corners: &[Vec3]) -> f32 {
+        let corners: Vec<Vec2> = corners
+            .iter()
+            .map(|&corner| world_to_canvas(corner))
+            .collect();
+        let min = corners
+            .iter()
+            .fold(Vec2::MAX, |min, &corner| min.min(corner));
+        let max = corners
+            .iter()
+            .fold(Vec2::MIN, |max, &corner| max.max(corner));
+        let (min, max) = (coverage_cell(min), coverage_cell(max));
+
+        let mut cells = 0;
+        let mut inked = 0;
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                if polygon_contains(&corners, cell_centre(cell)) {
+                    cells += 1;
+                    if self.is_inked(cell) {
+                        inked += 1;
+                    }
+                }
+            }
+        }
+        if cells == 0 {
+            return 0.0;
+        }
+        inked as f32 / cells as f32
+    }
+
+    fn is_inked(&self, cell: IVec2) -> bool {
+        cell_index(cell).is_some_and(|index| self.inked[index])
+    }
+
+    // marks the cells under one square brush stamp
+    fn stamp(&mut self, centre: Vec2, size: f32) {
+        let half = size * 0.5;
+        let (min, max) = (coverage_cell(centre - half), coverage_cell(centre + half));
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                if (cell_centre(cell) - centre).abs().max_element() > half {
+                    continue;
+                }
+                if let Some(index) = cell_index(cell) {
+                    self.inked[index] = true;
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.inked.fill(false);
+    }
+}
+
 #[derive(Component)]
 struct SecondaryCamera;
 
@@ -23,10 +98,28 @@ pub struct Paint;
 #[derive(Event)]
 pub struct ClearEvent;
 
+// Ink laid down between two points on the paper this frame
+#[derive(Event)]
+pub struct StrokeEvent {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub radius: f32,
+}
+
 const CANVAS_LAYER: RenderLayers = RenderLayers::layer(1);
+const BRUSH_SIZE: f32 = 26.0;
+const CHISEL_BRUSH_SIZE: f32 = 34.0;
+// the canvas is 600x1000 pixels over the 0.6x1.0 paper
+const CANVAS_SCALE: f32 = 1000.0;
+const CANVAS_SIZE: Vec2 = Vec2::new(600.0, 1000.0);
+// canvas pixels along each side of an ink coverage cell
+const COVERAGE_CELL: f32 = 4.0;
+const COVERAGE_COLUMNS: i32 = 150;
+const COVERAGE_ROWS: i32 = 250;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<BrushState>()
+        .init_resource::<InkCoverage>()
         .add_systems(Startup, setup)
         .add_systems(
             Update,
@@ -81,10 +174,16 @@ fn setup(
     ));
 }
 
-fn clear_page(_event: On<ClearEvent>, mut commands: Commands, query: Query<Entity, With<Paint>>) {
+fn clear_page(
+    _event: On<ClearEvent>,
+    mut commands: Commands,
+    query: Query<Entity, With<Paint>>,
+    mut coverage: ResMut<InkCoverage>,
+) {
     for entity in &query {
         commands.entity(entity).despawn();
     }
+    coverage.clear();
 }
 
 fn mouse_draw_system(
@@ -92,6 +191,8 @@ fn mouse_draw_system(
     marker_q: Single<(&Marker, &mut InkSupplyPercent), With<Marker>>,
     mut brush_state: ResMut<BrushState>,
     mut commands: Commands,
+    mut coverage: ResMut<InkCoverage>,
+    active_shift: Res<ActiveShift>,
 ) {
     if !buttons.pressed(MouseButton::Left) {
         brush_state.last_pos = None;
@@ -110,14 +211,13 @@ fn mouse_draw_system(
         return;
     }
 
-    let local_x = location.x;
-    let local_z = location.z - PAPER_POS.z;
+    let current_pos = world_to_canvas(location);
 
-    // Map to Texture Coordinates (0.6 world units = 600px -> Scale 1000)
-    // We negate local_z so that moving the mouse "forward" (+Z) maps correctly to the 2D canvas
-    let canvas_x = local_x * 1000.0;
-    let canvas_y = -local_z * 1000.0;
-    let current_pos = Vec2::new(canvas_x, canvas_y);
+    let brush_size = if active_shift.has_unlock(Unlock::ChiselTip) {
+        CHISEL_BRUSH_SIZE
+    } else {
+        BRUSH_SIZE
+    };
 
     // If we have a previous point, interpolate
     if let Some(last_pos) = brush_state.last_pos {
@@ -129,21 +229,24 @@ fn mouse_draw_system(
             let lerped_pos = last_pos.lerp(current_pos, i as f32 / steps as f32);
 
             commands.spawn((
-                Sprite::from_color(Color::srgb(0.0, 0.0, 0.0), Vec2::splat(26.0)),
+                Sprite::from_color(Color::srgb(0.0, 0.0, 0.0), Vec2::splat(brush_size)),
                 Transform::from_xyz(lerped_pos.x, lerped_pos.y, 0.0),
                 CANVAS_LAYER,
                 Paint,
                 DespawnOnExit(GameState::PLAYING),
             ));
+            coverage.stamp(lerped_pos, brush_size);
         }
+        commands.trigger(StrokeEvent {
+            from: canvas_to_world(last_pos),
+            to: canvas_to_world(current_pos),
+            radius: brush_size * 0.5 / CANVAS_SCALE,
+        });
 
-        if !ink_supply.1 {
-            // println!("{:?}", ink_supply.0);
-            let distance = last_pos.distance(current_pos);
-            ink_supply.0 -= distance / 20.0;
-            if ink_supply.0 < 0.0 {
-                ink_supply.0 = 0.0;
-            }
+        let distance = last_pos.distance(current_pos);
+        ink_supply.0 -= distance / 20.0;
+        if ink_supply.0 < 0.0 {
+            ink_supply.0 = 0.0;
         }
         // println!("{}", ink_supply.0);
     } else {
@@ -162,3 +265,49 @@ fn mouse_draw_system(
 
     brush_state.last_pos = Some(current_pos);
 }
+
+fn canvas_to_world(pos: Vec2) -> Vec3 {
+    Vec3::new(
+        pos.x / CANVAS_SCALE,
+        PAPER_POS.y,
+        PAPER_POS.z - pos.y / CANVAS_SCALE,
+    )
+}
+
+fn world_to_canvas(pos: Vec3) -> Vec2 {
+    let local_x = pos.x;
+    let local_z = pos.z - PAPER_POS.z;
+
+    // Map to Texture Coordinates (0.6 world units = 600px -> Scale 1000)
+    // We negate local_z so that moving the mouse "forward" (+Z) maps correctly to the 2D canvas
+    Vec2::new(local_x * CANVAS_SCALE, -local_z * CANVAS_SCALE)
+}
+
+fn coverage_cell(pos: Vec2) -> IVec2 {
+    ((pos + CANVAS_SIZE * 0.5) / COVERAGE_CELL)
+        .floor()
+        .as_ivec2()
+}
+
+fn cell_centre(cell: IVec2) -> Vec2 {
+    (cell.as_vec2() + 0.5) * COVERAGE_CELL - CANVAS_SIZE * 0.5
+}
+
+fn cell_index(cell: IVec2) -> Option<usize> {
+    let in_canvas = (0..COVERAGE_COLUMNS).contains(&cell.x) && (0..COVERAGE_ROWS).contains(&cell.y);
+    in_canvas.then(|| (cell.y * COVERAGE_COLUMNS + cell.x) as usize)
+}
+
+// even-odd test, counting the polygon edges crossed by a ray to the right of the point
+fn polygon_contains(corners: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    for (index, &a) in corners.iter().enumerate() {
+        let b = corners[(index + 1) % corners.len()];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::{
     ecs::event::Trigger,
     picking::events::{Click, Pointer},
@@ -16,24 +18,66 @@ use bevy_rich_text3d::{
     Weight,
 };
 
-use crate::{pen::Marker, text_asset::get_text_file};
+use crate::{
+    ActiveShift, CountdownTimer, GameMode, ShiftOutcome, ShiftResult, TIME_BONUS_PER_SECOND,
+    feedback::{FeedbackEvent, Feedbacks},
+    layout::{
+        FontMetrics, GlyphGrid, LINE_WIDTH, PAPER_FONT_FAMILY, PAPER_FONT_PATH, PageLayout,
+        TEXT_SCALE, TEXT_SIZE, distance_to_segment, layout_pages,
+    },
+    locale::Locale,
+    pen::{Marker, Surface},
+    procgen::EndlessRun,
+    settings::Settings,
+    shredder::SHREDDER_SLOT,
+    stack::PageFiledEvent,
+    stamp::{StampKind, StampMark},
+    template::Ink,
+    text_asset::get_text_file,
+};
 
 const BUTTON_MODEL_PATH: &str = "models/next_button.glb";
 pub const BTN_POS: Vec3 = Vec3::new(0.5, 0.78, 1.3);
-
-const MAX_LENGTH: i32 = 24;
-const MAX_HEIGHT: i32 = 25;
+// how far the marker has to drag the sheet towards the outbox to file it
+const DRAG_DISTANCE: f32 = 0.3;
+
+// page deadline is proportional to the length and number of targets on the page
+const PAGE_BASE_SECONDS: f32 = 8.0;
+const PAGE_SECONDS_PER_CHAR: f32 = 0.02;
+const PAGE_SECONDS_PER_TARGET: f32 = 0.25;
+// seconds taken off the shift clock for every page that runs out of time
+const LATE_PAGE_PENALTY: f32 = 10.0;
+// seconds taken off the shift clock for shredding a page that had to be kept
+const SHRED_PENALTY: f32 = 10.0;
+// how close the marker has to come to the shredder's slot to feed it the page
+const SHRED_REACH: f32 = 0.15;
+// room past a full line before the renderer would wrap it again, the lines are already broken
+// by the layout
+const TEXT_BOUNDS_HEADROOM: f32 = 1.25;
 
 use crate::paint::ClearEvent;
 
 use super::GameState;
 pub struct PageText {
-    pub pages: Vec<String>,
+    pub pages: Vec<PageLayout>,
 }
 
+// What happens to the page on the desk when it is taken off
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PageOutcome {
+    // into the outbox, anything left unredacted leaks
+    File,
+    // into the shredder, nothing on it leaks
+    Shred,
+}
+
+// Keeps the page on the desk, the tutorial holds it until it's time to turn it
+#[derive(Resource, Default)]
+pub struct HoldPage(pub bool);
+
 #[derive(Component)]
 struct GoNextPage {
-    go: bool,
+    go: Option<PageOutcome>,
     can_go: bool,
 }
 
@@ -103,65 +147,104 @@ struct GoNextPage {
 // }
 
 impl PageText {
-    pub fn from_text(full_text: String) -> Self {
-        let mut pages: Vec<String> = Vec::new();
-
-        let mut row = 0;
-        let mut col: i32 = 0;
-        let mut total_chars = 0;
-        let mut to_redact = false;
-        let mut current_page_string = String::new();
-        for word in full_text.split(" ") {
-            if row > MAX_HEIGHT {
-                pages.push(current_page_string.clone());
-                current_page_string = String::new();
-                row = 0;
-                col = 0
-            }
-            let word_string = word.to_string();
-            if col + word_string.len() as i32 > MAX_LENGTH {
-                row += 1;
-                col = 0;
-            }
-            for c in word_string.chars() {
-                if c == '<' {
-                    current_page_string.push(c);
-                    to_redact = true;
-                    continue;
-                } else if c == '>' {
-                    current_page_string.push(c);
-                    to_redact = false;
-                    continue;
-                }
-                current_page_string.push(c);
-
-                total_chars += 1;
-                col += 1;
-            }
-            current_page_string.push(' ');
-            col += 1;
+    pub fn from_text(full_text: String, metrics: &FontMetrics) -> Self {
+        Self {
+            pages: layout_pages(&full_text, metrics),
         }
-        Self { pages: pages }
     }
 }
 
 #[derive(Component)]
 pub struct Page {
     pub pages: PageText,
+    // glyphs of the page on the desk, indexed in layout order
+    pub characters: Vec<Character>,
+    pub grid: GlyphGrid,
+    // sensitive parts of the photos on the page
+    pub regions: Vec<SensitiveRegion>,
+    // stamps pressed onto the page on the desk
+    pub stamps: Vec<StampMark>,
     pub to_redact: u32,
     pub is_redacted: u32,
     pub total_chars: u32,
     pub page_num: i32,
 }
 
+impl Page {
+    // stamps the page on the desk asks for
+    pub fn required_stamps(&self) -> &[StampKind] {
+        usize::try_from(self.page_num)
+            .ok()
+            .and_then(|index| self.pages.pages.get(index))
+            .map(|layout| layout.required_stamps.as_slice())
+            .unwrap_or_default()
+    }
+
+    // whether the page on the desk is to be destroyed rather than filed
+    pub fn must_shred(&self) -> bool {
+        usize::try_from(self.page_num)
+            .ok()
+            .and_then(|index| self.pages.pages.get(index))
+            .is_some_and(|layout| layout.must_shred)
+    }
+
+    // characters within `radius` of the marker's path from `from` to `to`
+    pub fn characters_under_stroke(&self, from: Vec3, to: Vec3, radius: f32) -> Vec<usize> {
+        let (from, to) = (page_offset(from), page_offset(to));
+        self.grid
+            .query_stroke(from, to, radius)
+            .into_iter()
+            .filter(|&index| {
+                let offset = page_offset(self.characters[index].position);
+                distance_to_segment(offset, from, to) < radius
+            })
+            .collect()
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct PageScores {
     pub total_chars: u32,
     pub total_to_redact: u32,
     pub correctly_redacted: u32,
+    pub wrongly_redacted: u32,
+    // targets left unredacted on pages that have been filed
+    pub leaked: u32,
 
     pub page_redaction: u32,
     pub page_total: u32,
+
+    pub correct_stamps: u32,
+    pub wrong_stamps: u32,
+    // stamps a filed page asked for and never got
+    pub missed_stamps: u32,
+
+    // pages that had to be destroyed and were
+    pub correct_shreds: u32,
+    // pages that had to be kept and were destroyed
+    pub wrong_shreds: u32,
+    // pages that had to be destroyed and were filed
+    pub missed_shreds: u32,
+}
+
+impl PageScores {
+    // nothing got out of the office, nothing that had to be kept went into the shredder and
+    // every page carried the stamps it asked for
+    pub fn clean(&self) -> bool {
+        self.leaked == 0
+            && self.wrong_shreds == 0
+            && self.missed_shreds == 0
+            && self.missed_stamps == 0
+    }
+}
+
+// Deadline for the current page when page timers are turned on
+#[derive(Resource, Default)]
+pub struct PageTimer {
+    pub timer: Timer,
+    // seconds left over from the last page, added to the next deadline
+    pub carry_over: f32,
+    pub late_pages: u32,
 }
 
 // #[derive(Resource)]
@@ -169,11 +252,78 @@ pub struct PageScores {
 //     pages: Vec<String>
 // }
 
-#[derive(Component, Debug)]
+#[derive(Debug)]
 pub struct Character {
     pub to_redact: bool,
     pub is_redacted: bool,
     pub page_num: u32,
+    pub position: Vec3,
+}
+
+// Part of a photo that counts as redacted once enough of it is inked over
+#[derive(Debug)]
+pub struct SensitiveRegion {
+    pub corners: Vec<Vec3>,
+    pub is_redacted: bool,
+}
+
+impl SensitiveRegion {
+    pub fn centre(&self) -> Vec3 {
+        self.corners.iter().sum::<Vec3>() / self.corners.len() as f32
+    }
+}
+
+// Text, rules and photos drawn on the page on the desk
+#[derive(Component)]
+pub struct PageContent;
+
+// Lines of text share a material per ink and the text atlas, rules share one plane
+#[derive(Resource)]
+struct PaperMaterials {
+    typed: Handle<StandardMaterial>,
+    faded: Handle<StandardMaterial>,
+    pen: Handle<StandardMaterial>,
+    rule: Handle<StandardMaterial>,
+    rule_mesh: Handle<Mesh>,
+}
+
+impl PaperMaterials {
+    fn ink(&self, ink: Ink) -> Handle<StandardMaterial> {
+        match ink {
+            Ink::Typed => self.typed.clone(),
+            Ink::Faded => self.faded.clone(),
+            Ink::Pen => self.pen.clone(),
+        }
+    }
+}
+
+impl FromWorld for PaperMaterials {
+    fn from_world(world: &mut World) -> Self {
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let mut text_material = |color: Color| {
+            materials.add(StandardMaterial {
+                // Use the shared texture atlas for efficient rendering
+                base_color: color,
+                base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            })
+        };
+        let typed = text_material(Color::BLACK);
+        let faded = text_material(Color::srgb(0.4, 0.4, 0.42));
+        let pen = text_material(Color::srgb(0.08, 0.15, 0.55));
+        let rule = materials.add(Color::srgb(0.3, 0.3, 0.3));
+        let rule_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Plane3d::default().mesh().size(1.0, 1.0));
+        Self {
+            typed,
+            faded,
+            pen,
+            rule,
+            rule_mesh,
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -188,17 +338,25 @@ pub(super) fn plugin(app: &mut App) {
     })
     // .add_plugins(TouchTextMaterial3dPlugin)
     .insert_resource(LoadFonts {
-        font_paths: vec!["assets/fonts/SpaceMono-Regular.ttf".to_owned()],
+        font_paths: vec![PAPER_FONT_PATH.to_owned()],
         ..default()
     })
     .insert_resource(PageScores::default())
+    .init_resource::<PageTimer>()
+    .init_resource::<HoldPage>()
+    .init_resource::<PaperMaterials>()
     // .add_systems(Startup, setup_animation)
     .add_systems(OnEnter(GameState::PLAYING), (setup))
     .add_systems(
         FixedUpdate,
         check_redacted.run_if(in_state(GameState::PLAYING)),
     )
-    .add_systems(Update, check_button.run_if(in_state(GameState::PLAYING)))
+    .add_systems(
+        Update,
+        (check_button, drag_page, update_page_timer)
+            .chain()
+            .run_if(in_state(GameState::PLAYING)),
+    )
     .add_systems(FixedUpdate, next_page);
     // .add_systems(
     //     Update,
@@ -206,12 +364,14 @@ pub(super) fn plugin(app: &mut App) {
     // );
 }
 
-fn check_redacted(page_q: Query<&Character>) {
-    let total_redacted: i32 = page_q
+fn check_redacted(page: Single<&Page>) {
+    let total_redacted: i32 = page
+        .characters
         .iter()
         .map(|item| if item.is_redacted { 1 } else { 0 })
         .sum();
-    let to_redact: i32 = page_q
+    let to_redact: i32 = page
+        .characters
         .iter()
         .map(|item| {
             if item.to_redact & !item.is_redacted {
@@ -229,17 +389,139 @@ fn check_redacted(page_q: Query<&Character>) {
 
 pub const PAPER_POS: Vec3 = Vec3::new(0.0, 0.8, 1.0);
 
+// world position on the paper of a layout offset
+pub fn page_position(x: f32, y: f32) -> Vec3 {
+    (PAPER_POS
+        + Vec3 {
+            x: 0.261,
+            y: 0.0,
+            z: 0.4,
+        })
+        + Vec3::Y * 0.001
+        - (Vec3 { x, y: 0.0, z: y })
+}
+
+// inverse of page_position, the layout offset under a world position
+fn page_offset(position: Vec3) -> Vec2 {
+    let origin = page_position(0.0, 0.0);
+    Vec2::new(origin.x - position.x, origin.z - position.z)
+}
+
 fn next_page(
     mut commands: Commands,
-    chars: Query<(&Character, Entity)>,
+    contents: Query<Entity, With<PageContent>>,
     mut page: Single<&mut Page>,
+    paper: Single<&MeshMaterial3d<StandardMaterial>, With<Page>>,
     mut go_next_page: Single<&mut GoNextPage>,
+    paper_materials: Res<PaperMaterials>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
     mut page_scores: ResMut<PageScores>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut shift_result: ResMut<ShiftResult>,
+    mut countdown: ResMut<CountdownTimer>,
+    active_shift: Res<ActiveShift>,
+    mut endless_run: ResMut<EndlessRun>,
+    mut page_timer: ResMut<PageTimer>,
+    // grouped to stay within bevy's limit of sixteen system parameters
+    (settings, metrics, hold_page, locale): (
+        Res<Settings>,
+        Res<FontMetrics>,
+        Res<HoldPage>,
+        Res<Locale>,
+    ),
 ) {
-    if go_next_page.go {
-        go_next_page.go = false;
-        for (c, ent) in chars.iter() {
+    // however the page was sent off, a held page stays put, the first one is still dealt
+    if hold_page.0 && page.page_num >= 0 {
+        go_next_page.go = None;
+        return;
+    }
+    if let Some(outcome) = go_next_page.go.take() {
+        // whether the page just sent off was handled without a leak or a wrong shred
+        let mut page_clean = false;
+        if page.page_num >= 0 {
+            page_timer.carry_over = page_timer.timer.remaining_secs();
+            match outcome {
+                // anything still unredacted on the filed page has leaked
+                PageOutcome::File => {
+                    let page_leaked = page_scores.page_total - page_scores.page_redaction;
+                    page_scores.leaked += page_leaked;
+                    let missed_stamps = page
+                        .required_stamps()
+                        .iter()
+                        .filter(|&&kind| !page.stamps.iter().any(|mark| mark.kind == kind))
+                        .count() as u32;
+                    page_scores.missed_stamps += missed_stamps;
+                    if page.must_shred() {
+                        page_scores.missed_shreds += 1;
+                    }
+                    page_clean = page_leaked == 0 && missed_stamps == 0 && !page.must_shred();
+                }
+                PageOutcome::Shred => {
+                    page_clean = page.must_shred();
+                    if page.must_shred() {
+                        page_scores.correct_shreds += 1;
+                        commands.trigger(FeedbackEvent {
+                            feedback: Feedbacks::Correct,
+                            pos: SHREDDER_SLOT,
+                        });
+                    } else {
+                        page_scores.wrong_shreds += 1;
+                        countdown.0.tick(Duration::from_secs_f32(SHRED_PENALTY));
+                        commands.trigger(FeedbackEvent {
+                            feedback: Feedbacks::Wrong,
+                            pos: SHREDDER_SLOT,
+                        });
+                    }
+                }
+            }
+            commands.trigger(PageFiledEvent {
+                paper_color: page.pages.pages[page.page_num as usize]
+                    .template
+                    .paper_color(),
+                outcome,
+            });
+        }
+
+        // endless runs buy time with each clean page and never run out of pages
+        if active_shift.mode == GameMode::Endless {
+            if page_clean {
+                let bonus = Duration::from_secs_f32(endless_run.page_time_bonus());
+                let elapsed = countdown.0.elapsed().saturating_sub(bonus);
+                countdown.0.set_elapsed(elapsed);
+                endless_run.clean_pages += 1;
+            }
+            if page.page_num + 1 >= page.pages.pages.len() as i32 {
+                let document =
+                    endless_run.generate_document(page.pages.pages.len() as u32, &locale);
+                page.pages
+                    .pages
+                    .extend(PageText::from_text(document, &metrics).pages);
+            }
+        }
+
+        // every page has been processed, the shift is over, but only a clean one is a win
+        if page.page_num + 1 >= page.pages.pages.len() as i32 {
+            // late pages already cost shift time, like any other page the last one only fails
+            // the shift by what it leaks
+            if !page_scores.clean() {
+                shift_result.outcome = ShiftOutcome::Leaked;
+                shift_result.time_bonus = 0;
+            } else {
+                // the shift clock barely moves with page timers, the time saved on pages counts
+                let spare_secs = if settings.page_timers {
+                    page_timer.carry_over
+                } else {
+                    countdown.0.remaining_secs()
+                };
+                shift_result.outcome = ShiftOutcome::Completed;
+                shift_result.time_bonus = spare_secs as u32 * TIME_BONUS_PER_SECOND;
+            }
+            next_state.set(GameState::END);
+            return;
+        }
+
+        for ent in contents.iter() {
             commands.entity(ent).despawn();
         }
         commands.trigger(ClearEvent);
@@ -255,99 +537,152 @@ fn next_page(
             MeshMaterial3d<StandardMaterial>,
             Transform,
             Mesh3d,
-            Character,
+            PageContent,
             DespawnOnExit<GameState>,
         )> = Vec::new();
         page.page_num += 1;
-        let x_offset = 0.022;
-        let y_offset = 0.032;
-        let mut row = 0;
-        let mut col: i32 = 0;
-        let mut to_redact = false;
-        let mut total_to_redact = 0;
-        let mut total_chars = 0;
-        let mut chars_skipped: u32 = 0;
-        // If this line fails it means we have reached the end of the pages
-        let page_string = page
+        let page_num = page.page_num;
+        // the end of the pages is caught above, so this can't fail
+        let layout = page
             .pages
             .pages
-            .get(page.page_num as usize)
-            .expect("Can't get page at index");
-        for word in page_string.split(" ") {
-            if row > MAX_HEIGHT {
-                break;
-            }
-            let word_string = word.to_string();
-            if col + word_string.len() as i32 > MAX_LENGTH {
-                row += 1;
-                col = 0;
-            }
-            for c in word_string.chars() {
-                if c == '<' {
-                    to_redact = true;
-                    continue;
-                } else if c == '>' {
-                    to_redact = false;
-                    continue;
-                }
-                batch_spawn.push((
-                    Text3d::new(c),
-                    Text3dBounds { width: 260.0 },
-                    Text3dStyling {
-                        font: "monospace".into(),
-                        weight: Weight::BOLD,
-                        ..default()
-                    },
-                    MeshMaterial3d(materials.add(StandardMaterial {
-                        // Use the shared texture atlas for efficient rendering
-                        base_color: Color::BLACK,
-                        base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
-                        alpha_mode: AlphaMode::Blend,
-                        ..default()
-                    })),
-                    Transform::from_translation(
-                        ((PAPER_POS
-                            + Vec3 {
-                                x: 0.25,
-                                y: 0.0,
-                                z: 0.4,
-                            })
-                            + Vec3::Y * 0.001
-                            - (Vec3 {
-                                x: x_offset * col as f32,
-                                y: 0.0,
-                                z: y_offset * row as f32,
-                            })),
-                    )
+            .get(page_num as usize)
+            .expect("Can't get page at index")
+            .clone();
+        for line in &layout.lines {
+            batch_spawn.push((
+                Text3d::new(line.text.clone()),
+                Text3dBounds {
+                    width: LINE_WIDTH / (TEXT_SCALE * line.scale) * TEXT_BOUNDS_HEADROOM,
+                },
+                Text3dStyling {
+                    size: TEXT_SIZE,
+                    font: PAPER_FONT_FAMILY.into(),
+                    weight: Weight::BOLD,
+                    ..default()
+                },
+                MeshMaterial3d(paper_materials.ink(line.ink)),
+                Transform::from_translation(page_position(line.centre(), line.y))
                     .with_rotation(
-                        Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)
+                        Quat::from_rotation_y(-line.tilt)
+                            * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)
                             * Quat::from_rotation_z(std::f32::consts::PI),
                     )
-                    .with_scale(Vec3::splat(0.0025)),
-                    Mesh3d::default(),
-                    Character {
-                        to_redact: to_redact,
-                        is_redacted: false,
-                        page_num: page.page_num as u32,
-                    },
-                    DespawnOnExit(GameState::PLAYING),
-                ));
-                if to_redact {
-                    total_to_redact += 1;
-                }
-                total_chars += 1;
-                col += 1;
-            }
-            col += 1;
+                    .with_scale(Vec3::splat(TEXT_SCALE * line.scale)),
+                Mesh3d::default(),
+                PageContent,
+                DespawnOnExit(GameState::PLAYING),
+            ));
         }
+        commands.spawn_batch(batch_spawn);
+
+        // rules and photos sit just under the text
+        for rule in &layout.rules {
+            commands.spawn((
+                Mesh3d(paper_materials.rule_mesh.clone()),
+                MeshMaterial3d(paper_materials.rule.clone()),
+                Transform::from_translation(
+                    page_position(rule.center().x, rule.center().y) - Vec3::Y * 0.0002,
+                )
+                .with_scale(Vec3::new(rule.width(), 1.0, rule.height())),
+                PageContent,
+                DespawnOnExit(GameState::PLAYING),
+            ));
+        }
+        for photo in &layout.photos {
+            commands.spawn((
+                Mesh3d(paper_materials.rule_mesh.clone()),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color_texture: Some(asset_server.load(&photo.image)),
+                    unlit: true,
+                    ..default()
+                })),
+                Transform::from_translation(
+                    page_position(photo.rect.center().x, photo.rect.center().y) - Vec3::Y * 0.0004,
+                )
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::PI))
+                .with_scale(Vec3::new(
+                    photo.rect.width(),
+                    1.0,
+                    photo.rect.height(),
+                )),
+                PageContent,
+                DespawnOnExit(GameState::PLAYING),
+            ));
+        }
+        if let Some(material) = materials.get_mut(&paper.0) {
+            material.base_color = layout.template.paper_color();
+        }
+
+        page.grid = layout.grid.clone();
+        page.stamps.clear();
+        page.characters = layout
+            .glyphs
+            .iter()
+            .map(|glyph| Character {
+                to_redact: glyph.to_redact,
+                is_redacted: false,
+                page_num: page_num as u32,
+                position: page_position(glyph.centre(), glyph.y),
+            })
+            .collect();
+        page.regions = layout
+            .photos
+            .iter()
+            .flat_map(|photo| &photo.regions)
+            .map(|corners| SensitiveRegion {
+                corners: corners
+                    .iter()
+                    .map(|corner| page_position(corner.x, corner.y))
+                    .collect(),
+                is_redacted: false,
+            })
+            .collect();
+        let total_chars = page.characters.len() as u32;
+        let total_to_redact = page
+            .characters
+            .iter()
+            .filter(|character| character.to_redact)
+            .count() as u32
+            + page.regions.len() as u32;
         page.total_chars += total_chars;
         page.to_redact += total_to_redact;
-        commands.spawn_batch(batch_spawn);
 
         // update page score resource
         page_scores.total_chars += total_chars;
         page_scores.total_to_redact += total_to_redact;
-        page_scores.page_total += total_to_redact
+        page_scores.page_total += total_to_redact;
+
+        if settings.page_timers {
+            let deadline = PAGE_BASE_SECONDS
+                + total_chars as f32 * PAGE_SECONDS_PER_CHAR
+                + total_to_redact as f32 * PAGE_SECONDS_PER_TARGET
+                + page_timer.carry_over;
+            page_timer.timer = Timer::from_seconds(deadline, TimerMode::Once);
+        }
+    }
+}
+
+// files the page automatically once its deadline has passed
+fn update_page_timer(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut page_timer: ResMut<PageTimer>,
+    mut go_next_page: Single<&mut GoNextPage>,
+    mut countdown: ResMut<CountdownTimer>,
+    page: Single<&Page>,
+) {
+    // the deadline is only set once the first page is on the desk
+    if !settings.page_timers || page.page_num < 0 {
+        return;
+    }
+    if page_timer.timer.tick(time.delta()).just_finished() {
+        page_timer.late_pages += 1;
+        countdown.0.tick(Duration::from_secs_f32(LATE_PAGE_PENALTY));
+    }
+    // keep asking until next_page has run, check_button clears this every frame
+    if page_timer.timer.is_finished() {
+        go_next_page.go = Some(PageOutcome::File);
     }
 }
 
@@ -361,9 +696,17 @@ fn check_button(
 ) {
     // let marker = marker
     if let Some(tip_location) = marker.tip_location {
-        if tip_location.distance(BTN_POS) < 0.095 && go_next_page.can_go {
+        // the button files the page, the shredder's slot destroys it
+        let outcome = if tip_location.distance(BTN_POS) < 0.095 {
+            Some(PageOutcome::File)
+        } else if tip_location.distance(SHREDDER_SLOT) < SHRED_REACH {
+            Some(PageOutcome::Shred)
+        } else {
+            None
+        };
+        if outcome.is_some() && go_next_page.can_go {
             println!("NEXT_PAGE");
-            go_next_page.go = true;
+            go_next_page.go = outcome;
             go_next_page.can_go = false;
         //    let (to_play, ent) = ani_to_play.into_inner();
         //     for child in children.iter_descendants(ent) {
@@ -378,7 +721,7 @@ fn check_button(
         //         }
         //     }
         } else {
-            go_next_page.go = false
+            go_next_page.go = None
         }
     }
     if mouse_btn.just_released(MouseButton::Left) {
@@ -386,12 +729,40 @@ fn check_button(
     }
 }
 
+// dragging the sheet towards the outbox with the right button files it like the button does
+fn drag_page(
+    marker: Single<&Transform, With<Marker>>,
+    mouse_btn: Res<ButtonInput<MouseButton>>,
+    mut go_next_page: Single<&mut GoNextPage>,
+    mut drag_start: Local<Option<f32>>,
+) {
+    let x = marker.translation.x;
+    if mouse_btn.just_pressed(MouseButton::Right) && (x - PAPER_POS.x).abs() < 0.3 {
+        *drag_start = Some(x);
+    }
+    if !mouse_btn.pressed(MouseButton::Right) {
+        *drag_start = None;
+        return;
+    }
+    // the outbox sits towards -x
+    if drag_start.is_some_and(|start| start - x > DRAG_DISTANCE) {
+        go_next_page.go = Some(PageOutcome::File);
+        *drag_start = None;
+    }
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
     mut page_scores: ResMut<PageScores>,
+    mut shift_result: ResMut<ShiftResult>,
+    active_shift: Res<ActiveShift>,
+    endless_run: Res<EndlessRun>,
+    mut page_timer: ResMut<PageTimer>,
+    locale: Res<Locale>,
+    metrics: Res<FontMetrics>,
 ) {
     let ink_mesh_scene =
         SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(BUTTON_MODEL_PATH)));
@@ -399,9 +770,10 @@ fn setup(
     commands.spawn((
         ink_mesh_scene,
         GoNextPage {
-            go: true,
+            go: Some(PageOutcome::File),
             can_go: true,
         },
+        Surface,
         Transform::from_translation(BTN_POS)
             .with_scale(Vec3::splat(0.1))
             .with_rotation(Quat::from_rotation_y(3.14159)),
@@ -410,7 +782,11 @@ fn setup(
 
     // Text on the paper
     // let page_string = "That's all the family news that we're allowed to talk about. We really hope you'll come and visit us soon. I mean we're literally begging you to visit us. And make it quick before they <kill us> Now it's time for Christmas dinner - I think the robots sent us a pie! You know I love my soylent green.";
-    let page_string = get_text_file("assets/text/beemovie.txt").expect("CAN't LOAD BEE MOVIE");
+    let page_string = match active_shift.mode {
+        GameMode::Endless => endless_run.generate_document(0, &locale),
+        _ => get_text_file(&locale.document_path(&active_shift.document))
+            .expect("CAN't LOAD DOCUMENT"),
+    };
 
     // Paper
     commands.spawn((
@@ -418,21 +794,36 @@ fn setup(
         MeshMaterial3d(materials.add(Color::WHITE)),
         Transform::from_translation(PAPER_POS),
         Page {
-            pages: PageText::from_text(page_string.into()),
+            pages: PageText::from_text(page_string.into(), &metrics),
+            characters: Vec::new(),
+            grid: GlyphGrid::default(),
+            regions: Vec::new(),
+            stamps: Vec::new(),
             is_redacted: 0,
             to_redact: 0,
             total_chars: 0,
             page_num: -1,
         },
+        Surface,
         DespawnOnExit(GameState::PLAYING),
     ));
 
     // reset score state
     page_scores.correctly_redacted = 0;
+    page_scores.wrongly_redacted = 0;
+    page_scores.leaked = 0;
     page_scores.total_chars = 0;
     page_scores.total_to_redact = 0;
     page_scores.page_redaction = 0;
     page_scores.page_total = 0;
+    page_scores.correct_stamps = 0;
+    page_scores.wrong_stamps = 0;
+    page_scores.missed_stamps = 0;
+    page_scores.correct_shreds = 0;
+    page_scores.wrong_shreds = 0;
+    page_scores.missed_shreds = 0;
+    *shift_result = ShiftResult::default();
+    *page_timer = PageTimer::default();
 
     // commands.spawn((
     //     Text3d::new("123456789098765432123456789098765 In accordance with the determinations reached during the most recent closed procedural interval, all affected parties are advised that preliminary conditions have now been satisfied and that subsequent measures will proceed without further notice.\n\nAny variance from the established sequence, whether intentional or incidental, will be documented and reconciled under the appropriate review instruments. Stakeholders should consider this communication to constitute sufficient advisory of impending adjustments, the full scope of which will be disclosed only upon completion of the requisite confirmations."),
@@ -7,12 +7,20 @@ use bevy::{
 };
 
 use crate::{
-    CountdownTimer,
-    audio::{SoundBank, SoundEvent, Sounds, StopLoopEvent},
+    ActiveShift, CountdownTimer, GameMode,
+    audio::{
+        Bus, Emitter, LoopCommand, LoopEvent, LoopHandle, LoopSpec, SoundBank, Sounds, crossfade,
+        start_loop,
+    },
+    camera::CameraView,
+    campaign::Unlock,
+    cap::{CapEvent, CapState, MarkerCap},
     environment::Desk,
     feedback::{FeedbackEvent, Feedbacks},
-    paint::PaintPlane,
-    paper::{Character, Page, PageScores},
+    paint::{InkCoverage, PaintPlane, StrokeEvent},
+    paper::{PAPER_POS, Page, PageContent, PageScores},
+    shredder::SHREDDER_LID,
+    stamp::HeldStamp,
 };
 
 use super::GameState;
@@ -33,10 +41,10 @@ pub(super) fn plugin(app: &mut App) {
             Update,
             (
                 mouse_motion_system,
-                marker_animation_change,
                 setup_scene_once_loaded, // Does this really need to happen in an update, could we move it to startup?
                 update_ink_supply_meter,
                 pen_drop,
+                tilt_marker,
                 ray_cast_system,
             )
                 .run_if(in_state(GameState::PLAYING))
@@ -44,16 +52,72 @@ pub(super) fn plugin(app: &mut App) {
         )
         .add_systems(
             FixedUpdate,
-            (can_draw_check, check_refill, handle_sound_loops)
+            (can_draw_check, check_refill)
                 .run_if(in_state(GameState::PLAYING))
                 .chain(),
-        );
+        )
+        .add_systems(
+            Update,
+            handle_sound_loops
+                .after(ray_cast_system)
+                .run_if(in_state(GameState::PLAYING)),
+        )
+        .add_systems(
+            Update,
+            redact_regions
+                .run_if(in_state(GameState::PLAYING).and(resource_changed::<InkCoverage>)),
+        )
+        .add_observer(redact_stroke)
+        .add_observer(play_cap_animation);
 }
 
 // An example asset that contains a mesh and animation.
 const GLTF_PATH: &str = "models/marker_1_black.glb";
 const INK_MODEL_PATH: &str = "models/ink_res.glb";
 pub const INK_RES_POS: Vec3 = Vec3::new(-0.5, 0.8, 1.5);
+// fraction of a photo region that has to be inked before it counts
+const REGION_COVERAGE: f32 = 0.9;
+// how close the tip has to be to the ink well to dip into it
+const INK_WELL_REACH: f32 = 0.08;
+// the marker leans this far over, and its ray with it
+const TIP_TILT: f32 = 0.5;
+// the tip can reach anywhere on the desk top, in x and z
+const DESK_BOUNDS: Rect = Rect {
+    min: Vec2::new(-0.98, 0.27),
+    max: Vec2::new(0.98, 1.73),
+};
+// the shredder's lid and the strip between it and the desk, so the tip can slide across
+const SHREDDER_REACH: Rect = Rect {
+    min: Vec2::new(DESK_BOUNDS.max.x, SHREDDER_LID.min.y),
+    max: SHREDDER_LID.max,
+};
+// height of the marker above whatever is under its tip, pressed down and lifted
+const PRESSED_HEIGHT: f32 = 0.188;
+const LIFTED_HEIGHT: f32 = 0.3;
+// used when nothing is found under the tip
+const DESK_TOP: f32 = 0.75;
+// how quickly the marker rises over objects and settles back, per second
+const HEIGHT_SPEED: f32 = 20.0;
+// radians of lean per metre per second of movement, and the most it leans
+const MOTION_TILT: f32 = 0.15;
+const MAX_MOTION_TILT: f32 = 0.3;
+// how quickly the lean follows the marker's movement, per second
+const TILT_SMOOTHING: f32 = 10.0;
+// pen loops fade in and out over this long, so lifting the marker doesn't click
+const PEN_SOUND_FADE: f32 = 0.08;
+// tip speed in metres per second at which the scratch is at its loudest
+const SCRATCH_FULL_SPEED: f32 = 0.5;
+// the scratch plays slower when dragged slowly and faster when dashed across
+const SCRATCH_MIN_RATE: f32 = 0.8;
+const SCRATCH_MAX_RATE: f32 = 1.3;
+// how quickly the measured tip speed follows the marker, per second
+const SCRATCH_SMOOTHING: f32 = 15.0;
+// loops are only refaded once they've moved this far, not every frame
+const SCRATCH_STEP: f32 = 0.04;
+// ink percent below which the felt starts to scratch dry
+const DRY_INK: f32 = 15.0;
+// how long the wet scratch takes to give way to the dry one, and back after a refill
+const DRY_CROSSFADE: f32 = 0.4;
 
 // A component that stores a reference to an animation we want to play. This is
 // created when we start loading the mesh (see `setup_mesh_and_animation`) and
@@ -62,16 +126,19 @@ pub const INK_RES_POS: Vec3 = Vec3::new(-0.5, 0.8, 1.5);
 #[derive(Resource)]
 struct PenAnimations {
     animations: Vec<AnimationNodeIndex>,
-    current_annimation: usize,
     graph_handle: Handle<AnimationGraph>,
 }
 
 #[derive(Component)]
-pub struct InkSupplyPercent(pub f32, pub bool);
+pub struct InkSupplyPercent(pub f32);
 
 #[derive(Component)]
 struct InkSupplyMeter();
 
+// Something on or beside the desk that the marker rides over when its tip passes above it
+#[derive(Component)]
+pub struct Surface;
+
 #[derive(Component, Default, Clone, Copy)]
 pub struct Marker {
     pub tip_location: Option<Vec3>,
@@ -79,9 +146,32 @@ pub struct Marker {
     pub off_page: bool,
 }
 
+impl Marker {
+    pub fn in_ink_well(&self) -> bool {
+        self.tip_location
+            .is_some_and(|tip| tip.distance(INK_RES_POS) < INK_WELL_REACH)
+    }
+}
+
 #[derive(Component)]
 struct InkRes;
 
+// The marker's scratch and the ink well's slurp, each faded in and out on its own
+#[derive(Component)]
+struct PenSounds {
+    scratch: LoopHandle,
+    // whether the scratch playing is the dry one of a marker running out of ink
+    dry: bool,
+    slurp: LoopHandle,
+    slurping: bool,
+    // volume and rate last sent to the scratch
+    scratch_volume: f32,
+    rate: f32,
+    // smoothed tip speed across the page
+    speed: f32,
+    last_position: Option<Vec3>,
+}
+
 fn create_ink_meter(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -102,6 +192,8 @@ fn setup_mesh_and_animation(
     mut graphs: ResMut<Assets<AnimationGraph>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    sound_bank: Res<SoundBank>,
+    active_shift: Res<ActiveShift>,
 ) {
     // Create an animation graph containing a single animation. We want the "run"
     // animation from our example asset, which has an index of two.
@@ -116,7 +208,6 @@ fn setup_mesh_and_animation(
 
     commands.insert_resource(PenAnimations {
         animations: node_indices,
-        current_annimation: 1,
         graph_handle,
     });
 
@@ -129,18 +220,55 @@ fn setup_mesh_and_animation(
 
     // Spawn an entity with our components, and connect it to an observer that
     // will trigger when the scene is loaded and spawned.
-    commands.spawn((
-        Marker::default(),
-        InkSupplyPercent(100.0, false),
-        mesh_scene,
-        Transform::from_scale(Vec3::splat(0.03))
-            .with_rotation(Quat::from_rotation_z(0.5))
-            .with_translation(Vec3::new(0.0, 1.1, 1.0)),
-        DespawnOnExit(GameState::PLAYING),
-    ));
+    let marker = commands
+        .spawn((
+            Marker::default(),
+            // the tutorial starts by teaching how to take the cap off
+            if active_shift.mode == GameMode::Tutorial {
+                MarkerCap::capped()
+            } else {
+                MarkerCap::default()
+            },
+            InkSupplyPercent(100.0),
+            mesh_scene,
+            Transform::from_scale(Vec3::splat(0.03))
+                .with_rotation(Quat::from_rotation_z(TIP_TILT))
+                .with_translation(Vec3::new(0.0, 1.1, 1.0)),
+            DespawnOnExit(GameState::PLAYING),
+        ))
+        .id();
+    // the scratch comes from the marker's tip and the slurp from the ink well
+    let pen_sounds = PenSounds {
+        scratch: start_loop(
+            &mut commands,
+            &sound_bank,
+            Sounds::MarkerDrag,
+            Bus::Sfx,
+            Emitter::Follow(marker),
+            0.0,
+            0.0,
+        ),
+        dry: false,
+        slurp: start_loop(
+            &mut commands,
+            &sound_bank,
+            Sounds::Slurp,
+            Bus::Sfx,
+            Emitter::At(INK_RES_POS),
+            0.0,
+            0.0,
+        ),
+        slurping: false,
+        scratch_volume: 0.0,
+        rate: 1.0,
+        speed: 0.0,
+        last_position: None,
+    };
+    commands.entity(marker).insert(pen_sounds);
     // INK RES
     commands.spawn((
         InkRes,
+        Surface,
         ink_mesh_scene,
         Transform::from_scale(Vec3::new(0.1, 0.05, 0.1)).with_translation(INK_RES_POS),
         DespawnOnExit(GameState::PLAYING),
@@ -148,17 +276,14 @@ fn setup_mesh_and_animation(
 }
 
 fn ray_cast_system(
-    mut commands: Commands,
     mut raycast: MeshRayCast,
     mut pen_q: Single<(&Transform, &mut Marker), With<Marker>>,
-    mut characters: Query<(&mut Character, &Transform)>,
+    page_contents: Query<(), With<PageContent>>,
     mut desk_q: Query<&mut Desk>,
     mut page_q: Query<&mut Page>,
     ignore_q: Query<Entity, With<PaintPlane>>,
     mut gizmos: Gizmos,
     mouse: Res<ButtonInput<MouseButton>>,
-    mut countdown: ResMut<CountdownTimer>,
-    mut page_scores: ResMut<PageScores>,
 ) {
     // marker query
     let pen_transform = pen_q.0;
@@ -173,9 +298,10 @@ fn ray_cast_system(
 
     // setup ray cast with marker rotation
     // make sure that the drawing plane gets filtered out
-    let rot = Quat::from_rotation_z(0.5);
-    let dir_vec = rot * Vec3::NEG_Y;
-    let ray = Ray3d::new(pen_transform.translation, Dir3::new(dir_vec).unwrap());
+    let ray = Ray3d::new(
+        pen_transform.translation,
+        Dir3::new(tip_direction(pen_transform.rotation)).unwrap(),
+    );
     let filter = |entity| !ignore_q.contains(entity);
     let settings = MeshRayCastSettings::default().with_filter(&filter);
     let hits = raycast.cast_ray(ray, &settings);
@@ -191,89 +317,71 @@ fn ray_cast_system(
             continue;
         }
 
-        if let Ok(mut character) = characters.get_mut(*ent) {
+        if page_contents.contains(*ent) {
             marker.off_page = false;
-
-            if character.0.to_redact && !character.0.is_redacted {
-                // character.0.is_redacted = true;
-
-                // // update page scores
-                // page_scores.correctly_redacted += 1;
-                // page_scores.page_redaction += 1;
-
-                // match marker.tip_location {
-                //     Some(pos) => {
-                //         commands.trigger(FeedbackEvent {
-                //             feedback: Feedbacks::Correct,
-                //             pos: pos,
-                //         });
-                //     }
-                //     _ => {}
-                // }
-            } else if !character.0.is_redacted {
-                // character.0.is_redacted = true;
-
-                // // decrement counter if wrong character is redacted
-                // countdown.0.tick(Duration::from_secs(1));
-
-                // match marker.tip_location {
-                //     Some(pos) => {
-                //         commands.trigger(FeedbackEvent {
-                //             feedback: Feedbacks::Wrong,
-                //             pos: pos,
-                //         });
-                //     }
-                //     _ => {}
-                // }
-            }
-            // println!("redacted?, {}", character.to_redact);
         } else if let Ok(mut _desk) = page_q.get_mut(*ent) {
             marker.off_page = false;
         } else if let Ok(mut _desk) = desk_q.get_mut(*ent) {
             marker.off_page = true;
         }
     }
+}
 
-    if !marker.can_draw {
-        return;
+// redacts every character under the ink of a stroke, so scoring matches what's drawn
+fn redact_stroke(
+    stroke: On<StrokeEvent>,
+    mut commands: Commands,
+    mut page: Single<&mut Page>,
+    mut countdown: ResMut<CountdownTimer>,
+    mut page_scores: ResMut<PageScores>,
+) {
+    for index in page.characters_under_stroke(stroke.from, stroke.to, stroke.radius) {
+        let character = &mut page.characters[index];
+        if character.to_redact && !character.is_redacted {
+            character.is_redacted = true;
+
+            // update page scores
+            page_scores.correctly_redacted += 1;
+            page_scores.page_redaction += 1;
+
+            commands.trigger(FeedbackEvent {
+                feedback: Feedbacks::Correct,
+                pos: stroke.to,
+            });
+        } else if !character.is_redacted {
+            character.is_redacted = true;
+            page_scores.wrongly_redacted += 1;
+
+            // decrement counter if wrong character is redacted
+            countdown.0.tick(Duration::from_secs(1));
+
+            commands.trigger(FeedbackEvent {
+                feedback: Feedbacks::Wrong,
+                pos: stroke.to,
+            });
+        }
     }
-    if let Some(tip_position) = marker.tip_location {
-        for (mut character, transform) in characters {
-            if transform.translation.distance(tip_position) < 0.01 {
-                if character.to_redact && !character.is_redacted {
-                    character.is_redacted = true;
-
-                    // update page scores
-                    page_scores.correctly_redacted += 1;
-                    page_scores.page_redaction += 1;
-
-                    match marker.tip_location {
-                        Some(pos) => {
-                            commands.trigger(FeedbackEvent {
-                                feedback: Feedbacks::Correct,
-                                pos: pos,
-                            });
-                        }
-                        _ => {}
-                    }
-                } else if !character.is_redacted {
-                    character.is_redacted = true;
-
-                    // decrement counter if wrong character is redacted
-                    countdown.0.tick(Duration::from_secs(1));
-
-                    match marker.tip_location {
-                        Some(pos) => {
-                            commands.trigger(FeedbackEvent {
-                                feedback: Feedbacks::Wrong,
-                                pos: pos,
-                            });
-                        }
-                        _ => {}
-                    }
-                }
-            }
+}
+
+// a photo region is redacted once enough of it is under ink
+fn redact_regions(
+    mut commands: Commands,
+    mut page: Single<&mut Page>,
+    coverage: Res<InkCoverage>,
+    mut page_scores: ResMut<PageScores>,
+) {
+    for region in &mut page.regions {
+        if region.is_redacted || coverage.coverage(&region.corners) < REGION_COVERAGE {
+            continue;
         }
+        region.is_redacted = true;
+        page_scores.correctly_redacted += 1;
+        page_scores.page_redaction += 1;
+
+        commands.trigger(FeedbackEvent {
+            feedback: Feedbacks::Correct,
+            pos: region.centre(),
+        });
     }
 }
 
@@ -295,48 +403,116 @@ fn reset_mouse_setting(mut windows: Query<(&mut CursorOptions)>) {
     }
 }
 
+// the direction the marker's ray points in, from the marker down to its tip, leaning with it
+pub fn tip_direction(rotation: Quat) -> Vec3 {
+    rotation * Vec3::NEG_Y
+}
+
+// where the marker's ray meets a flat surface at height `surface`
+pub fn tip_spot(marker: &Transform, surface: f32) -> Vec3 {
+    let direction = tip_direction(marker.rotation);
+    marker.translation + direction * (marker.translation.y - surface) / -direction.y
+}
+
+// the marker rides over whatever is under its tip, the ink well and button included
 fn pen_drop(
-    mut commands: Commands,
+    mut raycast: MeshRayCast,
+    time: Res<Time>,
     mouse: Res<ButtonInput<MouseButton>>,
-    mut pen: Single<(&mut Transform, &mut Marker)>,
-    mut ink_meter: Single<&mut InkSupplyPercent>,
+    pen: Single<(&mut Transform, &mut Marker)>,
+    surfaces: Query<(), With<Surface>>,
+    parents: Query<&ChildOf>,
 ) {
-    if mouse.pressed(MouseButton::Left) {
-        pen.0.translation.y = 0.988;
+    let (mut transform, mut marker) = pen.into_inner();
+    let pressed = mouse.pressed(MouseButton::Left);
+    if !pressed {
+        marker.off_page = true;
+    }
+
+    // look straight down at the spot under the tip, only at the furniture and what's on it, so
+    // the marker, its meter and the floating feedback never lift it
+    let spot = tip_spot(&transform, DESK_TOP);
+    let ray = Ray3d::new(Vec3::new(spot.x, 2.0, spot.z), Dir3::NEG_Y);
+    let filter = |entity| {
+        surfaces.contains(entity)
+            || parents
+                .iter_ancestors(entity)
+                .any(|ancestor| surfaces.contains(ancestor))
+    };
+    let settings = MeshRayCastSettings::default()
+        .with_filter(&filter)
+        .with_visibility(RayCastVisibility::Any);
+    let surface = raycast
+        .cast_ray(ray, &settings)
+        .first()
+        .map_or(DESK_TOP, |(_, hit)| hit.point.y);
+
+    let height = if pressed {
+        PRESSED_HEIGHT
+    } else {
+        LIFTED_HEIGHT
+    };
+    let target = surface + height;
+    let blend = (HEIGHT_SPEED * time.delta_secs()).min(1.0);
+    // pressing down is immediate, only rising and settling are eased
+    transform.translation.y = if pressed && target < transform.translation.y {
+        target
     } else {
-        pen.0.translation.y = 1.1;
+        transform.translation.y + (target - transform.translation.y) * blend
+    };
+}
 
-        // reset refilling and off_page states
-        pen.1.off_page = true;
-        ink_meter.1 = false;
+// the marker leans into the direction it's moving, like a hand dragging it
+fn tilt_marker(
+    time: Res<Time>,
+    mut marker: Single<&mut Transform, With<Marker>>,
+    mut last_position: Local<Option<Vec3>>,
+    mut velocity: Local<Vec3>,
+) {
+    let delta = time.delta_secs();
+    let position = marker.translation;
+    if let Some(last) = *last_position {
+        if delta > 0.0 {
+            let moved = (position - last).with_y(0.0) / delta;
+            *velocity += (moved - *velocity) * (TILT_SMOOTHING * delta).min(1.0);
+        }
     }
+    *last_position = Some(position);
+
+    let lean = (*velocity * MOTION_TILT).clamp_length_max(MAX_MOTION_TILT);
+    // moving along z tips the marker about x and moving along x tips it about z
+    marker.rotation = Quat::from_rotation_x(lean.z)
+        * Quat::from_rotation_z(-lean.x)
+        * Quat::from_rotation_z(TIP_TILT);
+}
+
+// the first clip has the cap on, the second has it off
+fn cap_animation(state: CapState) -> usize {
+    if state == CapState::Capped { 0 } else { 1 }
 }
 
-fn marker_animation_change(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+fn play_cap_animation(
+    event: On<CapEvent>,
     mut animation_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
-    mut animations: ResMut<PenAnimations>,
+    animations: Res<PenAnimations>,
 ) {
+    let animation = cap_animation(event.to);
+    if animation == cap_animation(event.from) {
+        return;
+    }
     for (mut player, mut transitions) in &mut animation_players {
-        let Some((&playing_animation_index, _)) = player.playing_animations().next() else {
-            continue;
-        };
-        if keyboard_input.just_pressed(KeyCode::Enter) {
-            animations.current_annimation =
-                (animations.current_annimation + 1) % animations.animations.len();
-
-            transitions.play(
-                &mut player,
-                animations.animations[animations.current_annimation],
-                Duration::from_millis(1),
-            );
-        }
+        transitions.play(
+            &mut player,
+            animations.animations[animation],
+            Duration::from_millis(1),
+        );
     }
 }
 
 fn setup_scene_once_loaded(
     mut commands: Commands,
     animations: Res<PenAnimations>,
+    cap: Single<&MarkerCap>,
     mut players: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
 ) {
     if !animations.is_changed() {
@@ -349,7 +525,11 @@ fn setup_scene_once_loaded(
         // component. The `AnimationTransitions` component wants to manage all
         // the animations and will get confused if the animations are started
         // directly via the `AnimationPlayer`.
-        transitions.play(&mut player, animations.animations[1], Duration::ZERO);
+        transitions.play(
+            &mut player,
+            animations.animations[cap_animation(cap.state())],
+            Duration::ZERO,
+        );
 
         commands
             .entity(entity)
@@ -374,23 +554,34 @@ fn update_ink_supply_meter(
     transform.scale.y = meter_scale
 }
 
-fn check_refill(marker_q: Single<(&Marker, &mut InkSupplyPercent)>) {
-    let (marker, mut ink_supply) = marker_q.into_inner();
-    if let Some(tip_location) = marker.tip_location {
-        if tip_location.distance(INK_RES_POS) < 0.08 {
-            ink_supply.0 += 1.0;
-            ink_supply.1 = true;
-        } else {
-            ink_supply.1 = false;
-        }
+// only an open marker dipped in the well takes up ink
+fn check_refill(
+    marker_q: Single<(&MarkerCap, &mut InkSupplyPercent)>,
+    active_shift: Res<ActiveShift>,
+) {
+    let (cap, mut ink_supply) = marker_q.into_inner();
+    if cap.state() != CapState::Refilling {
+        return;
     }
+    let refill_rate = if active_shift.has_unlock(Unlock::InkBottle) {
+        2.0
+    } else {
+        1.0
+    };
+    ink_supply.0 = (ink_supply.0 + refill_rate).min(100.0);
 }
 
-fn can_draw_check(single: Single<(&mut Marker, &InkSupplyPercent)>, pen_anim: Res<PenAnimations>) {
-    let (mut marker, ink_sup) = single.into_inner();
-    if ink_sup.0 <= 0.0 {
+fn can_draw_check(
+    single: Single<(&mut Marker, &MarkerCap, &InkSupplyPercent)>,
+    held_stamp: Res<HeldStamp>,
+) {
+    let (mut marker, cap, ink_sup) = single.into_inner();
+    // the hand holding a stamp isn't drawing
+    if held_stamp.0.is_some() {
         marker.can_draw = false;
-    } else if pen_anim.current_annimation == 0 {
+    } else if ink_sup.0 <= 0.0 {
+        marker.can_draw = false;
+    } else if !cap.state().can_draw() {
         marker.can_draw = false
     } else {
         marker.can_draw = true;
@@ -399,9 +590,10 @@ fn can_draw_check(single: Single<(&mut Marker, &InkSupplyPercent)>, pen_anim: Re
 
 fn mouse_motion_system(
     accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
+    view: Res<CameraView>,
     mut marker: Single<&mut Transform, With<Marker>>,
 ) {
-    let delta = accumulated_mouse_motion.delta;
+    let delta = accumulated_mouse_motion.delta * view.motion_scale();
     if delta != Vec2::ZERO {
         // println!("{:?}", delta);
         marker.translation += Vec3 {
@@ -410,39 +602,126 @@ fn mouse_motion_system(
             z: -delta.y / 600.0,
         };
         // println!("{:?}", marker.translation);
+
+        // keep the tip over the desk, or over the shredder beside it
+        let reach = tip_spot(&marker, DESK_TOP) - marker.translation;
+        let tip = marker.translation.xz() + reach.xz();
+        let on_desk = tip.clamp(DESK_BOUNDS.min, DESK_BOUNDS.max);
+        let on_shredder = tip.clamp(SHREDDER_REACH.min, SHREDDER_REACH.max);
+        let allowed = if tip.distance(on_shredder) < tip.distance(on_desk) {
+            on_shredder
+        } else {
+            on_desk
+        };
+        let corrected = allowed - reach.xz();
+        marker.translation.x = corrected.x;
+        marker.translation.z = corrected.y;
     }
 }
 
+// the scratch follows how fast the tip moves over the page and goes dry as the ink runs out
 fn handle_sound_loops(
     mut commands: Commands,
-    m_q: Single<&Marker>,
-    i_q: Single<&InkSupplyPercent>,
+    time: Res<Time>,
     sound_bank: Res<SoundBank>,
+    m_q: Single<(
+        Entity,
+        &Marker,
+        &MarkerCap,
+        &Transform,
+        &InkSupplyPercent,
+        &mut PenSounds,
+    )>,
+    held_stamp: Res<HeldStamp>,
 ) {
-    let refilling = i_q.1;
-    let can_draw = m_q.can_draw;
-    let off_page = m_q.off_page;
+    let (marker_entity, marker, cap, transform, ink_supply, mut pen_sounds) = m_q.into_inner();
+    let slurping = cap.state() == CapState::Refilling;
+
+    if slurping != pen_sounds.slurping {
+        pen_sounds.slurping = slurping;
+        commands.trigger(LoopEvent {
+            handle: pen_sounds.slurp,
+            command: LoopCommand::Fade {
+                volume: if slurping { 1.0 } else { 0.0 },
+                seconds: PEN_SOUND_FADE,
+            },
+        });
+    }
 
-    if refilling {
-        if sound_bank.looping {
-            return;
-        }
-        commands.trigger(SoundEvent {
-            sound: Sounds::Slurp,
-            setting: PlaybackSettings::LOOP,
+    // only a pressed, uncapped tip on the paper makes a sound
+    let on_paper = marker.tip_location.is_some_and(|tip| {
+        let offset = tip - PAPER_POS;
+        offset.x.abs() < 0.3 && offset.z.abs() < 0.5
+    });
+    let touching = on_paper && held_stamp.0.is_none() && cap.state().can_draw();
+
+    let position = transform.translation;
+    let delta = time.delta_secs();
+    // only sliding across the page counts, not the marker dropping onto it
+    let moved = pen_sounds
+        .last_position
+        .map_or(0.0, |last| (position - last).with_y(0.0).length());
+    pen_sounds.last_position = Some(position);
+    if delta > 0.0 {
+        let speed = if touching { moved / delta } else { 0.0 };
+        let blend = (SCRATCH_SMOOTHING * delta).min(1.0);
+        pen_sounds.speed += (speed - pen_sounds.speed) * blend;
+    }
+    let motion = (pen_sounds.speed / SCRATCH_FULL_SPEED).clamp(0.0, 1.0);
+
+    let scratch_volume = if touching { motion } else { 0.0 };
+    let rate = SCRATCH_MIN_RATE + (SCRATCH_MAX_RATE - SCRATCH_MIN_RATE) * motion;
+
+    // a marker running out of ink scratches dry until it's refilled
+    let dry = ink_supply.0 < DRY_INK;
+    if dry != pen_sounds.dry {
+        pen_sounds.dry = dry;
+        pen_sounds.scratch = crossfade(
+            &mut commands,
+            &sound_bank,
+            pen_sounds.scratch,
+            LoopSpec {
+                sound: if dry {
+                    Sounds::MarkerDry
+                } else {
+                    Sounds::MarkerDrag
+                },
+                bus: Bus::Sfx,
+                emitter: Emitter::Follow(marker_entity),
+                volume: pen_sounds.scratch_volume,
+            },
+            DRY_CROSSFADE,
+        );
+        commands.trigger(LoopEvent {
+            handle: pen_sounds.scratch,
+            command: LoopCommand::Speed(pen_sounds.rate),
         });
-    } else if !off_page && can_draw {
-        if sound_bank.looping {
-            return;
-        }
-        commands.trigger(SoundEvent {
-            sound: Sounds::MarkerDrag,
-            setting: PlaybackSettings::LOOP,
+    }
+
+    if needs_update(pen_sounds.scratch_volume, scratch_volume) {
+        pen_sounds.scratch_volume = scratch_volume;
+        commands.trigger(LoopEvent {
+            handle: pen_sounds.scratch,
+            command: LoopCommand::Fade {
+                volume: scratch_volume,
+                seconds: PEN_SOUND_FADE,
+            },
         });
+    }
+    if needs_update(pen_sounds.rate, rate) {
+        pen_sounds.rate = rate;
+        commands.trigger(LoopEvent {
+            handle: pen_sounds.scratch,
+            command: LoopCommand::Speed(rate),
+        });
+    }
+}
+
+// silence is always sent, anything else only once it has moved far enough to hear
+fn needs_update(sent: f32, value: f32) -> bool {
+    if value == 0.0 {
+        sent != 0.0
     } else {
-        if !sound_bank.looping {
-            return;
-        }
-        commands.trigger(StopLoopEvent);
+        (value - sent).abs() >= SCRATCH_STEP
     }
 }
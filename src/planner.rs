@@ -9,15 +9,21 @@ use bevy_rich_text3d::{Text3d, Text3dBounds, Text3dStyling, TextAtlas, Weight};
 use bevy_sprite3d::{Sprite3d, Sprite3dPlugin};
 
 use super::GameState;
+use crate::audio::DuckEvent;
 use crate::loading::GameAssets;
+use crate::locale::Locale;
 use crate::paper::{Page, PageScores};
-use crate::{CountdownTimer, LIFETIME};
+use crate::procgen::EndlessRun;
+use crate::settings::Settings;
+use crate::{ActiveShift, CountdownTimer, GameMode};
 
 // pub const PLANNER_POS: Vec3 = Vec3::new(0.65, 0.78, 0.9);
 pub const BOSS_POS: Vec3 = Vec3::new(-4.0, 0.5, 7.0);
 pub const PLANNER_POS: Vec3 = Vec3::new(-3.0, 2.0, 7.0);
 pub const BOB_VALUE: f32 = 0.1;
 const BOSS_MODEL_PATH: &str = "models/boss.glb";
+// how long the boss talks over the mob while handing out the directive
+const BRIEFING_SECONDS: f32 = 4.0;
 
 #[derive(Component)]
 struct Planner;
@@ -112,6 +118,11 @@ fn setup(
             .with_rotation(Quat::from_rotation_y(2.8)),
         DespawnOnExit(GameState::PLAYING),
     ));
+
+    // the mob quietens while the boss gives the directive
+    commands.trigger(DuckEvent {
+        seconds: BRIEFING_SECONDS,
+    });
 }
 
 fn bob_boss(
@@ -132,16 +143,54 @@ fn update_scores(
     score_res: Res<PageScores>,
     mut text3d: Single<&mut Text3d, With<PlannerText>>,
     page: Single<&Page>,
+    active_shift: Res<ActiveShift>,
+    endless_run: Res<EndlessRun>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
 ) {
     if score_res.is_changed() {
         let correct_redacted = score_res.page_redaction;
         let unredacted = score_res.page_total - correct_redacted;
+        let running_score = match active_shift.mode {
+            GameMode::Endless => format!(
+                "\n{}",
+                locale.format(
+                    "planner-score",
+                    &[("score", endless_run.score(&score_res).to_string())]
+                )
+            ),
+            _ => String::new(),
+        };
+
+        // the boss still hands out the directive when the scores are only on screen
+        let scores = if settings.readouts.diegetic() {
+            format!(
+                "\n\n{}\n{}\n{}{}",
+                locale.format(
+                    "planner-page",
+                    &[
+                        ("page", (page.page_num + 1).to_string()),
+                        ("pages", page.pages.pages.len().to_string()),
+                    ],
+                ),
+                locale.format(
+                    "planner-redacted",
+                    &[("count", correct_redacted.to_string())]
+                ),
+                locale.format("planner-unredacted", &[("count", unredacted.to_string())]),
+                running_score
+            )
+        } else {
+            String::new()
+        };
 
-        text3d.segments = Text3d::new(format!("Boss:\nRedact anything related to Bees NOW\nPress Space to change view\n\nPage {}/{}\nRedacted: {}\nUnredacted: {}",
-        page.page_num + 1,
-        page.pages.pages.len(),
-        correct_redacted,
-        unredacted)
-     ).segments;
+        text3d.segments = Text3d::new(format!(
+            "{}\n{}\n{}{}",
+            locale.get("planner-boss"),
+            active_shift.directive,
+            locale.get("planner-hint"),
+            scores
+        ))
+        .segments;
     }
 }
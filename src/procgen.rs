@@ -0,0 +1,285 @@
+use std::fs;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{ActiveShift, GameMode, GameState, locale::Locale, paper::PageScores};
+
+const SAVE_DIR: &str = "saves";
+const HIGH_SCORE_PATH: &str = "saves/highscores.txt";
+const MAX_HIGH_SCORES: usize = 10;
+
+pub const ENDLESS_LIFETIME: f32 = 40.0;
+// seconds handed back for filing a page, shrinks as the run goes on
+const PAGE_TIME_BONUS: f32 = 20.0;
+const PAGE_TIME_DECAY: f32 = 1.5;
+const MIN_PAGE_TIME_BONUS: f32 = 6.0;
+
+const POINTS_PER_CORRECT: i32 = 10;
+const POINTS_PER_WRONG: i32 = 5;
+const POINTS_PER_PAGE: i32 = 50;
+const POINTS_PER_LEAK: i32 = 10;
+
+const NAMES: [&str; 16] = [
+    "Voss",
+    "Pike",
+    "Quill",
+    "Ohm",
+    "Marr",
+    "Tan",
+    "Hollis",
+    "Crane",
+    "Fenwick",
+    "Abara",
+    "Lindqvist",
+    "Moreau",
+    "Okafor",
+    "Petrov",
+    "Delacroix",
+    "Wren",
+];
+const PLACES: [&str; 12] = [
+    "Castlegate",
+    "Greywater",
+    "Thornfield",
+    "Bramble",
+    "Saint-Odo",
+    "Wendmouth",
+    "Hollowmere",
+    "Ashcombe",
+    "Kettering",
+    "Dunmoor",
+    "Redhaven",
+    "Ironbridge",
+];
+const CODEWORDS: [&str; 12] = [
+    "NIGHTJAR",
+    "HONEYCOMB",
+    "BLUEGLASS",
+    "TALLOW",
+    "MARIGOLD",
+    "COPPERHEAD",
+    "SILENTBELL",
+    "DRYDOCK",
+    "LANTERN",
+    "PALEHORSE",
+    "WAXWING",
+    "ORCHARD",
+];
+// sentence templates and filler topics are in the string tables, as `procgen-sentence-<n>` and
+// `procgen-filler-<n>`, slots are filled from the word lists above
+const SENTENCE_COUNT: usize = 16;
+const FILLER_COUNT: usize = 10;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TargetRule {
+    Names,
+    Places,
+    Codewords,
+}
+
+impl TargetRule {
+    // locale key of the boss's instructions
+    pub fn directive(&self) -> &'static str {
+        match self {
+            TargetRule::Names => "endless-names",
+            TargetRule::Places => "endless-places",
+            TargetRule::Codewords => "endless-codewords",
+        }
+    }
+
+    fn slot(&self) -> &'static str {
+        match self {
+            TargetRule::Names => "name",
+            TargetRule::Places => "place",
+            TargetRule::Codewords => "code",
+        }
+    }
+}
+
+// State of the current endless run
+#[derive(Resource)]
+pub struct EndlessRun {
+    pub rule: TargetRule,
+    // pages sent off with nothing leaked, only these earn points and time
+    pub clean_pages: u32,
+}
+
+impl Default for EndlessRun {
+    fn default() -> Self {
+        let rules = [TargetRule::Names, TargetRule::Places, TargetRule::Codewords];
+        Self {
+            rule: rules[rand::rng().random_range(0..rules.len())],
+            clean_pages: 0,
+        }
+    }
+}
+
+impl EndlessRun {
+    pub fn active_shift(&self, locale: &Locale) -> ActiveShift {
+        ActiveShift {
+            mode: GameMode::Endless,
+            document: String::new(),
+            directive: locale.get(self.rule.directive()),
+            lifetime: ENDLESS_LIFETIME,
+            unlocks: Vec::new(),
+        }
+    }
+
+    pub fn score(&self, page_scores: &PageScores) -> i32 {
+        page_scores.correctly_redacted as i32 * POINTS_PER_CORRECT
+            - page_scores.wrongly_redacted as i32 * POINTS_PER_WRONG
+            - page_scores.leaked as i32 * POINTS_PER_LEAK
+            + self.clean_pages as i32 * POINTS_PER_PAGE
+    }
+
+    // seconds added to the countdown when a page is filed clean
+    pub fn page_time_bonus(&self) -> f32 {
+        (PAGE_TIME_BONUS - self.clean_pages as f32 * PAGE_TIME_DECAY).max(MIN_PAGE_TIME_BONUS)
+    }
+
+    // builds the text of one document in the player's language, with targets for the active
+    // rule wrapped in <>
+    pub fn generate_document(&self, page_index: u32, locale: &Locale) -> String {
+        let mut rng = rand::rng();
+
+        // later pages have more sentences, more of which contain targets
+        let sentence_count = 6 + (page_index / 3).min(4) as usize;
+        let target_chance = (0.35 + page_index as f32 * 0.07).min(0.9);
+
+        let sentences: Vec<String> = (1..=SENTENCE_COUNT)
+            .map(|index| locale.get(&format!("procgen-sentence-{index}")))
+            .collect();
+        let hot: Vec<&String> = sentences
+            .iter()
+            .filter(|sentence| slots(sentence).any(|slot| slot == self.rule.slot()))
+            .collect();
+
+        let header = ["procgen-memo", "procgen-email", "procgen-note"][rng.random_range(0..3)];
+        let mut text = locale.format(header, &[("page", (page_index + 1).to_string())]);
+        text.push(' ');
+        for _ in 0..sentence_count {
+            let sentence = if !hot.is_empty() && rng.random_bool(target_chance as f64) {
+                hot[rng.random_range(0..hot.len())]
+            } else {
+                &sentences[rng.random_range(0..sentences.len())]
+            };
+            text.push_str(sentence);
+            text.push(' ');
+        }
+
+        self.fill_slots(&text, locale, &mut rng)
+    }
+
+    fn fill_slots(&self, template: &str, locale: &Locale, rng: &mut impl Rng) -> String {
+        let mut text = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            let slot = slot_name(&rest[start..start + end + 1]);
+            let word = match slot {
+                "name" => NAMES[rng.random_range(0..NAMES.len())].to_owned(),
+                "place" => PLACES[rng.random_range(0..PLACES.len())].to_owned(),
+                "code" => CODEWORDS[rng.random_range(0..CODEWORDS.len())].to_owned(),
+                _ => locale.get(&format!(
+                    "procgen-filler-{}",
+                    rng.random_range(1..=FILLER_COUNT)
+                )),
+            };
+            if slot == self.rule.slot() {
+                text.push('<');
+                text.push_str(&word);
+                text.push('>');
+            } else {
+                text.push_str(&word);
+            }
+            rest = &rest[start + end + 1..];
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+// `{ $name }` in a template names the slot `name`
+fn slot_name(placeable: &str) -> &str {
+    placeable
+        .trim_matches(['{', '}'])
+        .trim()
+        .trim_start_matches('$')
+}
+
+fn slots(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(placeable, _)| slot_name(placeable))
+}
+
+// Local high score table, best first
+#[derive(Resource, Default)]
+pub struct HighScores {
+    pub scores: Vec<i32>,
+    // position of the last submitted score, if it made the table
+    pub last_rank: Option<usize>,
+    pub last_score: i32,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(load_high_scores())
+        .init_resource::<EndlessRun>()
+        .add_systems(OnExit(GameState::PLAYING), submit_score);
+}
+
+fn load_high_scores() -> HighScores {
+    let mut high_scores = HighScores::default();
+    if let Ok(contents) = fs::read_to_string(HIGH_SCORE_PATH) {
+        high_scores.scores = contents
+            .lines()
+            .filter_map(|line| line.trim().parse().ok())
+            .collect();
+    }
+    high_scores
+}
+
+fn save_high_scores(high_scores: &HighScores) {
+    let contents: String = high_scores
+        .scores
+        .iter()
+        .map(|score| format!("{score}\n"))
+        .collect();
+    if let Err(err) =
+        fs::create_dir_all(SAVE_DIR).and_then(|_| fs::write(HIGH_SCORE_PATH, contents))
+    {
+        println!("Failed to save high scores: {err}");
+    }
+}
+
+fn submit_score(
+    active_shift: Res<ActiveShift>,
+    endless_run: Res<EndlessRun>,
+    page_scores: Res<PageScores>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    high_scores.last_rank = None;
+    if active_shift.mode != GameMode::Endless {
+        return;
+    }
+
+    let score = endless_run.score(&page_scores);
+    let rank = high_scores
+        .scores
+        .iter()
+        .position(|&best| score > best)
+        .unwrap_or(high_scores.scores.len());
+    high_scores.last_score = score;
+    if rank < MAX_HIGH_SCORES {
+        high_scores.scores.insert(rank, score);
+        high_scores.scores.truncate(MAX_HIGH_SCORES);
+        high_scores.last_rank = Some(rank);
+        save_high_scores(&high_scores);
+    }
+}
@@ -0,0 +1,444 @@
+use std::fs;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use super::GameState;
+use crate::{
+    audio::Bus,
+    locale::{Language, Locale, UI_FONT_PATH},
+};
+
+const SAVE_DIR: &str = "saves";
+const SETTINGS_PATH: &str = "saves/settings.txt";
+// bus volumes step down by this much per click, and wrap from silent back to full
+const VOLUME_STEP: u32 = 25;
+
+// Where the clock and the scores are shown during a shift
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Readouts {
+    // on the alarm clock and the boss's planner
+    #[default]
+    Diegetic,
+    // on an overlay in the corner of the screen
+    Hud,
+    Both,
+}
+
+impl Readouts {
+    const ALL: [Readouts; 3] = [Readouts::Diegetic, Readouts::Hud, Readouts::Both];
+
+    fn code(&self) -> &'static str {
+        match self {
+            Readouts::Diegetic => "diegetic",
+            Readouts::Hud => "hud",
+            Readouts::Both => "both",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|readouts| readouts.code() == code)
+    }
+
+    fn name_key(&self) -> &'static str {
+        match self {
+            Readouts::Diegetic => "readouts-diegetic",
+            Readouts::Hud => "readouts-hud",
+            Readouts::Both => "readouts-both",
+        }
+    }
+
+    fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|readouts| readouts == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn diegetic(&self) -> bool {
+        *self != Readouts::Hud
+    }
+
+    pub fn hud(&self) -> bool {
+        *self != Readouts::Diegetic
+    }
+}
+
+// How quickly the camera eases from one view to the next
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum CameraSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl CameraSpeed {
+    const ALL: [CameraSpeed; 3] = [CameraSpeed::Slow, CameraSpeed::Normal, CameraSpeed::Fast];
+
+    fn code(&self) -> &'static str {
+        match self {
+            CameraSpeed::Slow => "slow",
+            CameraSpeed::Normal => "normal",
+            CameraSpeed::Fast => "fast",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|speed| speed.code() == code)
+    }
+
+    fn name_key(&self) -> &'static str {
+        match self {
+            CameraSpeed::Slow => "camera-speed-slow",
+            CameraSpeed::Normal => "camera-speed-normal",
+            CameraSpeed::Fast => "camera-speed-fast",
+        }
+    }
+
+    fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|speed| speed == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    // multiplies the camera's transition speed
+    pub fn factor(&self) -> f32 {
+        match self {
+            CameraSpeed::Slow => 0.5,
+            CameraSpeed::Normal => 1.0,
+            CameraSpeed::Fast => 2.0,
+        }
+    }
+}
+
+// Player options, saved between sessions
+#[derive(Resource)]
+pub struct Settings {
+    // every page gets its own deadline instead of one timer for the shift
+    pub page_timers: bool,
+    pub language: Language,
+    // pushing the lifted marker to the edge of the screen turns the camera
+    pub free_look: bool,
+    pub camera_speed: CameraSpeed,
+    pub readouts: Readouts,
+    // percent for each mixer bus
+    volumes: HashMap<Bus, u32>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            page_timers: false,
+            language: Language::default(),
+            free_look: false,
+            camera_speed: CameraSpeed::default(),
+            readouts: Readouts::default(),
+            volumes: Bus::ALL.into_iter().map(|bus| (bus, 100)).collect(),
+        }
+    }
+}
+
+#[derive(Component, Debug, PartialEq, Eq, Copy, Clone)]
+enum SettingOption {
+    PageTimers,
+    Language,
+    FreeLook,
+    CameraSpeed,
+    Readouts,
+    Volume(Bus),
+}
+
+impl Settings {
+    // linear gain of a mixer bus
+    pub fn volume(&self, bus: Bus) -> f32 {
+        self.volumes.get(&bus).copied().unwrap_or(100) as f32 / 100.0
+    }
+
+    fn label(&self, option: SettingOption, locale: &Locale) -> String {
+        match option {
+            SettingOption::PageTimers => {
+                let state = if self.page_timers {
+                    locale.get("state-on")
+                } else {
+                    locale.get("state-off")
+                };
+                locale.format("settings-page-timers", &[("state", state)])
+            }
+            SettingOption::Language => locale.format(
+                "settings-language",
+                &[("language", self.language.name().to_owned())],
+            ),
+            SettingOption::FreeLook => {
+                let state = if self.free_look {
+                    locale.get("state-on")
+                } else {
+                    locale.get("state-off")
+                };
+                locale.format("settings-free-look", &[("state", state)])
+            }
+            SettingOption::CameraSpeed => locale.format(
+                "settings-camera-speed",
+                &[("speed", locale.get(self.camera_speed.name_key()))],
+            ),
+            SettingOption::Readouts => locale.format(
+                "settings-readouts",
+                &[("readouts", locale.get(self.readouts.name_key()))],
+            ),
+            SettingOption::Volume(bus) => locale.format(
+                "settings-volume",
+                &[
+                    ("bus", locale.get(bus.name_key())),
+                    ("volume", self.volumes[&bus].to_string()),
+                ],
+            ),
+        }
+    }
+
+    fn cycle(&mut self, option: SettingOption) {
+        match option {
+            SettingOption::PageTimers => self.page_timers = !self.page_timers,
+            SettingOption::Language => self.language = self.language.next(),
+            SettingOption::FreeLook => self.free_look = !self.free_look,
+            SettingOption::CameraSpeed => self.camera_speed = self.camera_speed.next(),
+            SettingOption::Readouts => self.readouts = self.readouts.next(),
+            SettingOption::Volume(bus) => {
+                let volume = self.volumes.entry(bus).or_insert(100);
+                *volume = if *volume == 0 {
+                    100
+                } else {
+                    volume.saturating_sub(VOLUME_STEP)
+                };
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+enum SettingsAction {
+    Cycle(SettingOption),
+    Back,
+}
+
+// Colours
+const TITLE_COLOR: Color = Color::WHITE;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(load_settings())
+        .add_systems(OnEnter(GameState::SETTINGS), settings_setup)
+        .add_systems(
+            Update,
+            (settings_action, button_system, update_labels)
+                .chain()
+                .run_if(in_state(GameState::SETTINGS)),
+        );
+}
+
+fn load_settings() -> Settings {
+    let mut settings = Settings::default();
+    if let Ok(contents) = fs::read_to_string(SETTINGS_PATH) {
+        for line in contents.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                match key.trim() {
+                    "page_timers" => settings.page_timers = value.trim() == "true",
+                    "free_look" => settings.free_look = value.trim() == "true",
+                    "camera_speed" => {
+                        settings.camera_speed =
+                            CameraSpeed::from_code(value.trim()).unwrap_or_default();
+                    }
+                    "readouts" => {
+                        settings.readouts = Readouts::from_code(value.trim()).unwrap_or_default();
+                    }
+                    "language" => {
+                        settings.language = Language::from_code(value.trim()).unwrap_or_default();
+                    }
+                    key => {
+                        if let Some(bus) = key.strip_prefix("volume_").and_then(Bus::from_code) {
+                            if let Ok(volume) = value.trim().parse::<u32>() {
+                                settings.volumes.insert(bus, volume.min(100));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    settings
+}
+
+fn save_settings(settings: &Settings) {
+    let mut contents = format!(
+        "page_timers={}\nlanguage={}\nfree_look={}\ncamera_speed={}\nreadouts={}\n",
+        settings.page_timers,
+        settings.language.code(),
+        settings.free_look,
+        settings.camera_speed.code(),
+        settings.readouts.code()
+    );
+    for bus in Bus::ALL {
+        contents += &format!("volume_{}={}\n", bus.code(), settings.volumes[&bus]);
+    }
+    if let Err(err) = fs::create_dir_all(SAVE_DIR).and_then(|_| fs::write(SETTINGS_PATH, contents))
+    {
+        println!("Failed to save settings: {err}");
+    }
+}
+
+fn settings_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+) {
+    commands.spawn((DespawnOnExit(GameState::SETTINGS), Camera2d));
+
+    let button_node = Node {
+        width: px(400),
+        // short enough for every option to fit on a 720p window
+        height: px(44),
+        margin: UiRect::all(px(5)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let ui_font: Handle<Font> = asset_server.load(UI_FONT_PATH);
+    let button_text_font = TextFont {
+        font: ui_font.clone(),
+        font_size: 28.0,
+        ..default()
+    };
+    let background_image = asset_server.load("menu/main.png");
+
+    let options = [
+        SettingOption::PageTimers,
+        SettingOption::Language,
+        SettingOption::FreeLook,
+        SettingOption::CameraSpeed,
+        SettingOption::Readouts,
+        SettingOption::Volume(Bus::Music),
+        SettingOption::Volume(Bus::Ambience),
+        SettingOption::Volume(Bus::Sfx),
+        SettingOption::Volume(Bus::Ui),
+    ];
+
+    let column = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(locale.get("settings-title")),
+                TextFont {
+                    font: ui_font,
+                    font_size: 67.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::all(px(25)),
+                    ..default()
+                },
+            ));
+
+            for option in options {
+                parent.spawn((
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    SettingsAction::Cycle(option),
+                    children![(
+                        Text::new(settings.label(option, &locale)),
+                        button_text_font.clone(),
+                        TextColor(TEXT_COLOR),
+                        option,
+                    )],
+                ));
+            }
+            parent.spawn((
+                Button,
+                button_node,
+                BackgroundColor(NORMAL_BUTTON),
+                SettingsAction::Back,
+                children![(
+                    Text::new(locale.get("button-back")),
+                    button_text_font,
+                    TextColor(TEXT_COLOR)
+                )],
+            ));
+        })
+        .id();
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::SETTINGS),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ImageNode::new(background_image),
+        ))
+        .add_child(column);
+}
+
+// This system handles changing all buttons color based on mouse interaction
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut background_color) in &mut interaction_query {
+        *background_color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON.into(),
+            Interaction::Hovered => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+fn settings_action(
+    interaction_query: Query<(&Interaction, &SettingsAction), (Changed<Interaction>, With<Button>)>,
+    mut settings: ResMut<Settings>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match action {
+                SettingsAction::Cycle(option) => {
+                    settings.cycle(*option);
+                    save_settings(&settings);
+                }
+                SettingsAction::Back => {
+                    game_state.set(GameState::MENU);
+                }
+            }
+        }
+    }
+}
+
+// the locale reloads after a language change, so relabel on either
+fn update_labels(
+    settings: Res<Settings>,
+    locale: Res<Locale>,
+    mut labels: Query<(&mut Text, &SettingOption)>,
+) {
+    if !settings.is_changed() && !locale.is_changed() {
+        return;
+    }
+    for (mut text, option) in &mut labels {
+        text.0 = settings.label(*option, &locale);
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+use crate::{GameState, paper::PageOutcome, pen::Surface, stack::PageFiledEvent};
+
+// stands on the floor beside the desk, past the stack of unprocessed pages
+const SHREDDER_POS: Vec3 = Vec3::new(1.15, 0.0, 1.3);
+const SHREDDER_SIZE: Vec3 = Vec3::new(0.25, 0.85, 0.4);
+// the page goes in through the middle of the lid
+pub const SHREDDER_SLOT: Vec3 = Vec3::new(
+    SHREDDER_POS.x,
+    SHREDDER_POS.y + SHREDDER_SIZE.y,
+    SHREDDER_POS.z,
+);
+// the lid in x and z, so the marker can reach over it from the desk
+pub const SHREDDER_LID: Rect = Rect {
+    min: Vec2::new(
+        SHREDDER_POS.x - SHREDDER_SIZE.x * 0.5,
+        SHREDDER_POS.z - SHREDDER_SIZE.z * 0.5,
+    ),
+    max: Vec2::new(
+        SHREDDER_POS.x + SHREDDER_SIZE.x * 0.5,
+        SHREDDER_POS.z + SHREDDER_SIZE.z * 0.5,
+    ),
+};
+const SHAKE_SECONDS: f32 = 0.6;
+const SHAKE_AMOUNT: f32 = 0.006;
+const SHAKE_SPEED: f32 = 90.0;
+
+// Rattles for a moment whenever it is fed a page
+#[derive(Component)]
+struct Shredder {
+    shake: Timer,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(Update, shake.run_if(in_state(GameState::PLAYING)))
+        .add_observer(start_shaking);
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut shake = Timer::from_seconds(SHAKE_SECONDS, TimerMode::Once);
+    shake.finish();
+    commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(SHREDDER_SIZE))),
+            MeshMaterial3d(materials.add(Color::srgb(0.18, 0.18, 0.2))),
+            Transform::from_translation(SHREDDER_POS + Vec3::Y * SHREDDER_SIZE.y * 0.5),
+            Shredder { shake },
+            Surface,
+            DespawnOnExit(GameState::PLAYING),
+        ))
+        .with_children(|parent| {
+            // Slot
+            parent.spawn((
+                Mesh3d(meshes.add(Cuboid::new(0.02, 0.002, SHREDDER_SIZE.z * 0.8))),
+                MeshMaterial3d(materials.add(Color::BLACK)),
+                Transform::from_xyz(0.0, SHREDDER_SIZE.y * 0.5, 0.0),
+            ));
+        });
+}
+
+fn start_shaking(filed: On<PageFiledEvent>, mut shredder: Single<&mut Shredder>) {
+    if filed.outcome == PageOutcome::Shred {
+        shredder.shake.reset();
+    }
+}
+
+fn shake(time: Res<Time>, shredder: Single<(&mut Shredder, &mut Transform)>) {
+    let (mut shredder, mut transform) = shredder.into_inner();
+    shredder.shake.tick(time.delta());
+    let base = SHREDDER_POS + Vec3::Y * SHREDDER_SIZE.y * 0.5;
+    let amount = if shredder.shake.is_finished() {
+        0.0
+    } else {
+        SHAKE_AMOUNT * (1.0 - shredder.shake.fraction())
+    };
+    transform.translation =
+        base + Vec3::X * amount * (shredder.shake.elapsed_secs() * SHAKE_SPEED).sin();
+}
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveShift, GameMode, GameState,
+    paper::{PAPER_POS, Page, PageOutcome},
+    pen::Surface,
+    shredder::SHREDDER_SLOT,
+};
+
+// footprint of the sheets in the piles, the paper shrunk to fit beside it
+const SHEET_SIZE: Vec2 = Vec2::new(0.33, 0.55);
+const SHEET_THICKNESS: f32 = 0.003;
+// piles stop growing past this, so long documents don't tower over the desk
+const MAX_PILE_SHEETS: u32 = 40;
+// endless runs never run out, so their stack always looks well stocked
+const ENDLESS_STACK_SHEETS: u32 = 25;
+const DESK_TOP: f32 = 0.75;
+// unprocessed pages beside the next button, filed ones on the ink side
+const STACK_POS: Vec3 = Vec3::new(0.68, DESK_TOP, 0.75);
+const OUTBOX_POS: Vec3 = Vec3::new(-0.68, DESK_TOP, 0.75);
+const FLIGHT_SECONDS: f32 = 0.45;
+// how far above the desk a filed sheet arcs on its way to the outbox
+const FLIGHT_HEIGHT: f32 = 0.15;
+
+// Sent when the page on the desk is filed or shredded
+#[derive(Event)]
+pub struct PageFiledEvent {
+    pub paper_color: Color,
+    pub outcome: PageOutcome,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum PileKind {
+    Stack,
+    Outbox,
+}
+
+// A pile of sheets drawn as one block, as tall as the sheets in it
+#[derive(Component)]
+struct Pile {
+    kind: PileKind,
+    sheets: u32,
+}
+
+// A page on its way from the desk to the outbox or the shredder
+#[derive(Component)]
+struct FlyingSheet {
+    timer: Timer,
+    outcome: PageOutcome,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (update_stack, fly_sheets, resize_piles)
+                .chain()
+                .run_if(in_state(GameState::PLAYING)),
+        )
+        .add_observer(file_sheet);
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sheet_mesh = meshes.add(Cuboid::new(SHEET_SIZE.x, 1.0, SHEET_SIZE.y));
+    let paper_material = materials.add(Color::srgb(0.95, 0.95, 0.92));
+    for (kind, position) in [(PileKind::Stack, STACK_POS), (PileKind::Outbox, OUTBOX_POS)] {
+        commands.spawn((
+            Mesh3d(sheet_mesh.clone()),
+            MeshMaterial3d(paper_material.clone()),
+            Transform::from_translation(position),
+            Visibility::Hidden,
+            Pile { kind, sheets: 0 },
+            Surface,
+            DespawnOnExit(GameState::PLAYING),
+        ));
+    }
+
+    // Outbox tray
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(SHEET_SIZE.x + 0.04, 0.01, SHEET_SIZE.y + 0.04))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.2, 0.22))),
+        Transform::from_translation(OUTBOX_POS - Vec3::Y * 0.004),
+        Surface,
+        DespawnOnExit(GameState::PLAYING),
+    ));
+}
+
+// the stack holds whatever pages are still to come
+fn update_stack(
+    page: Single<&Page, Changed<Page>>,
+    active_shift: Res<ActiveShift>,
+    mut piles: Query<&mut Pile>,
+) {
+    let remaining = if active_shift.mode == GameMode::Endless {
+        ENDLESS_STACK_SHEETS
+    } else {
+        (page.pages.pages.len() as i32 - page.page_num - 1).max(0) as u32
+    };
+    for mut pile in &mut piles {
+        if pile.kind == PileKind::Stack && pile.sheets != remaining {
+            pile.sheets = remaining;
+        }
+    }
+}
+
+fn file_sheet(
+    filed: On<PageFiledEvent>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(0.6, 1.0))),
+        MeshMaterial3d(materials.add(filed.paper_color)),
+        Transform::from_translation(PAPER_POS),
+        FlyingSheet {
+            timer: Timer::from_seconds(FLIGHT_SECONDS, TimerMode::Once),
+            outcome: filed.outcome,
+        },
+        DespawnOnExit(GameState::PLAYING),
+    ));
+}
+
+// sheets arc over to the outbox, shrinking to the pile's size, and join it when they land,
+// shredded ones drop into the shredder's slot instead
+fn fly_sheets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sheets: Query<(Entity, &mut FlyingSheet, &mut Transform)>,
+    mut piles: Query<&mut Pile>,
+) {
+    for (entity, mut sheet, mut transform) in &mut sheets {
+        sheet.timer.tick(time.delta());
+        let t = sheet.timer.fraction();
+        let eased = t * t * (3.0 - 2.0 * t);
+
+        let target = match sheet.outcome {
+            PageOutcome::File => {
+                let outbox_sheets = piles
+                    .iter()
+                    .find(|pile| pile.kind == PileKind::Outbox)
+                    .map_or(0, |pile| pile.sheets);
+                OUTBOX_POS + Vec3::Y * (pile_height(outbox_sheets) + SHEET_THICKNESS)
+            }
+            PageOutcome::Shred => SHREDDER_SLOT,
+        };
+        transform.translation = PAPER_POS.lerp(target, eased)
+            + Vec3::Y * FLIGHT_HEIGHT * (t * std::f32::consts::PI).sin();
+        transform.rotation = Quat::from_rotation_y(0.4 * (t * std::f32::consts::PI).sin());
+        let shrink = SHEET_SIZE / Vec2::new(0.6, 1.0);
+        let scale = Vec2::ONE.lerp(shrink, eased);
+        transform.scale = Vec3::new(scale.x, 1.0, scale.y);
+
+        if sheet.timer.is_finished() {
+            commands.entity(entity).despawn();
+            if sheet.outcome == PageOutcome::Shred {
+                continue;
+            }
+            for mut pile in &mut piles {
+                if pile.kind == PileKind::Outbox {
+                    pile.sheets += 1;
+                }
+            }
+        }
+    }
+}
+
+fn resize_piles(mut piles: Query<(&Pile, &mut Transform, &mut Visibility), Changed<Pile>>) {
+    for (pile, mut transform, mut visibility) in &mut piles {
+        let height = pile_height(pile.sheets);
+        let base = match pile.kind {
+            PileKind::Stack => STACK_POS,
+            PileKind::Outbox => OUTBOX_POS,
+        };
+        transform.translation = base + Vec3::Y * height * 0.5;
+        transform.scale = Vec3::new(1.0, height.max(f32::EPSILON), 1.0);
+        *visibility = if pile.sheets == 0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn pile_height(sheets: u32) -> f32 {
+    sheets.min(MAX_PILE_SHEETS) as f32 * SHEET_THICKNESS
+}
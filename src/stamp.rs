@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rich_text3d::{Text3d, Text3dBounds, Text3dStyling, TextAtlas, Weight};
+use rand::Rng;
+
+use crate::{
+    CountdownTimer, GameState,
+    feedback::{FeedbackEvent, Feedbacks},
+    layout::PAPER_FONT_FAMILY,
+    paper::{PAPER_POS, Page, PageContent, PageScores},
+    pen::Marker,
+};
+
+// where the stamps rest along the far edge of the desk
+const STAMP_RACK_Z: f32 = 1.62;
+const STAMP_SPACING: f32 = 0.15;
+const STAMP_SIZE: Vec3 = Vec3::new(0.08, 0.05, 0.06);
+// how close the marker has to come to a stamp to pick it up
+const PICK_UP_DISTANCE: f32 = 0.06;
+// the carried stamp hangs beside the marker, clear of its ray
+const CARRY_OFFSET: Vec3 = Vec3::new(-0.12, -0.05, 0.0);
+const MARK_SCALE: f32 = 0.006;
+// marks land a little crooked, up to this many radians either way
+const MARK_TILT: f32 = 0.2;
+
+// An approval stamp, a document can ask for one with `[stamp:approved]`
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum StampKind {
+    Approved,
+    Classified,
+    Shred,
+}
+
+impl StampKind {
+    const ALL: [StampKind; 3] = [StampKind::Approved, StampKind::Classified, StampKind::Shred];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "approved" => Some(StampKind::Approved),
+            "classified" => Some(StampKind::Classified),
+            "shred" => Some(StampKind::Shred),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            StampKind::Approved => "APPROVED",
+            StampKind::Classified => "CLASSIFIED",
+            StampKind::Shred => "SHRED",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            StampKind::Approved => Color::srgb(0.1, 0.55, 0.2),
+            StampKind::Classified => Color::srgb(0.75, 0.1, 0.1),
+            StampKind::Shred => Color::srgb(0.1, 0.1, 0.1),
+        }
+    }
+}
+
+// A stamp pressed onto the page on the desk
+#[derive(Debug, Clone)]
+pub struct StampMark {
+    pub kind: StampKind,
+    pub position: Vec3,
+}
+
+// The stamp the player is carrying instead of drawing with the marker
+#[derive(Resource, Default)]
+pub struct HeldStamp(pub Option<StampKind>);
+
+#[derive(Component)]
+struct StampTool {
+    kind: StampKind,
+    home: Vec3,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<HeldStamp>()
+        .add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(
+            Update,
+            (pick_up_stamp, use_stamp, carry_stamp)
+                .chain()
+                .run_if(in_state(GameState::PLAYING)),
+        );
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut held: ResMut<HeldStamp>,
+) {
+    held.0 = None;
+
+    let mesh = meshes.add(Cuboid::from_size(STAMP_SIZE));
+    for (index, kind) in StampKind::ALL.into_iter().enumerate() {
+        let home = Vec3::new(
+            (index as f32 - 1.0) * STAMP_SPACING,
+            0.75 + STAMP_SIZE.y * 0.5,
+            STAMP_RACK_Z,
+        );
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(materials.add(kind.color())),
+            Transform::from_translation(home),
+            StampTool { kind, home },
+            DespawnOnExit(GameState::PLAYING),
+        ));
+
+        // name of the stamp on the desk in front of it
+        commands.spawn((
+            mark_text(kind, &mut materials),
+            Transform::from_translation(home - Vec3::new(0.0, STAMP_SIZE.y * 0.5 - 0.001, 0.06))
+                .with_rotation(mark_rotation(0.0))
+                .with_scale(Vec3::splat(MARK_SCALE * 0.3)),
+            DespawnOnExit(GameState::PLAYING),
+        ));
+    }
+}
+
+// touching a stamp with the marker picks it up, touching its spot again puts it back
+fn pick_up_stamp(
+    marker: Single<&Marker>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    tools: Query<&StampTool>,
+    mut held: ResMut<HeldStamp>,
+    mut handled: Local<bool>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        *handled = false;
+        return;
+    }
+    let Some(tip_location) = marker.tip_location else {
+        return;
+    };
+    if *handled {
+        return;
+    }
+    for tool in &tools {
+        if tip_location.distance(tool.home) < PICK_UP_DISTANCE {
+            held.0 = if held.0 == Some(tool.kind) {
+                None
+            } else {
+                Some(tool.kind)
+            };
+            *handled = true;
+        }
+    }
+}
+
+// presses the carried stamp onto the page, once per click
+fn use_stamp(
+    mut commands: Commands,
+    marker: Single<&Marker>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    held: Res<HeldStamp>,
+    mut page: Single<&mut Page>,
+    mut page_scores: ResMut<PageScores>,
+    mut countdown: ResMut<CountdownTimer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut stamped: Local<bool>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        *stamped = false;
+        return;
+    }
+    let (Some(kind), Some(tip_location)) = (held.0, marker.tip_location) else {
+        return;
+    };
+    let offset = tip_location - PAPER_POS;
+    if *stamped || offset.x.abs() > 0.3 || offset.z.abs() > 0.5 || page.page_num < 0 {
+        return;
+    }
+    *stamped = true;
+
+    let position = Vec3::new(tip_location.x, PAPER_POS.y + 0.0015, tip_location.z);
+    commands.spawn((
+        mark_text(kind, &mut materials),
+        Transform::from_translation(position)
+            .with_rotation(mark_rotation(
+                rand::rng().random_range(-MARK_TILT..MARK_TILT),
+            ))
+            .with_scale(Vec3::splat(MARK_SCALE)),
+        PageContent,
+        DespawnOnExit(GameState::PLAYING),
+    ));
+
+    // a second mark of the same kind changes nothing
+    let already_stamped = page.stamps.iter().any(|mark| mark.kind == kind);
+    page.stamps.push(StampMark { kind, position });
+    if already_stamped {
+        return;
+    }
+    if page.required_stamps().contains(&kind) {
+        page_scores.correct_stamps += 1;
+        commands.trigger(FeedbackEvent {
+            feedback: Feedbacks::Correct,
+            pos: position,
+        });
+    } else {
+        page_scores.wrong_stamps += 1;
+        countdown.0.tick(Duration::from_secs(1));
+        commands.trigger(FeedbackEvent {
+            feedback: Feedbacks::Wrong,
+            pos: position,
+        });
+    }
+}
+
+// the held stamp follows the marker, the rest sit in the rack
+fn carry_stamp(
+    marker: Single<&Transform, (With<Marker>, Without<StampTool>)>,
+    held: Res<HeldStamp>,
+    mut tools: Query<(&StampTool, &mut Transform)>,
+) {
+    for (tool, mut transform) in &mut tools {
+        transform.translation = if held.0 == Some(tool.kind) {
+            marker.translation + CARRY_OFFSET
+        } else {
+            tool.home
+        };
+    }
+}
+
+fn mark_text(
+    kind: StampKind,
+    materials: &mut Assets<StandardMaterial>,
+) -> (
+    Text3d,
+    Text3dBounds,
+    Text3dStyling,
+    Mesh3d,
+    MeshMaterial3d<StandardMaterial>,
+) {
+    (
+        Text3d::new(kind.label()),
+        Text3dBounds { width: 400.0 },
+        Text3dStyling {
+            font: PAPER_FONT_FAMILY.into(),
+            weight: Weight::BOLD,
+            ..default()
+        },
+        Mesh3d::default(),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: kind.color(),
+            base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })),
+    )
+}
+
+// flat on the desk and readable from the chair, like the lines of a page
+fn mark_rotation(tilt: f32) -> Quat {
+    Quat::from_rotation_y(-tilt)
+        * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)
+        * Quat::from_rotation_z(std::f32::consts::PI)
+}
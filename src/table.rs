@@ -0,0 +1,84 @@
+// Redaction rules for sheets and forms, given in the tag like `@sheet:SSN;Amount>10000`
+
+// What a cell has to hold for a rule to redact it
+#[derive(Debug, PartialEq, Clone)]
+pub enum CellTest {
+    // every cell in the column
+    Any,
+    Over(f32),
+    Under(f32),
+    Equals(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RedactionRule {
+    // column header or form label, `*` matches any column
+    column: Option<String>,
+    test: CellTest,
+}
+
+impl RedactionRule {
+    // rules are split with `;`, and `_` stands in for spaces in headers
+    pub fn parse_list(argument: &str) -> Vec<Self> {
+        argument
+            .split(';')
+            .filter(|rule| !rule.is_empty())
+            .filter_map(|rule| {
+                let parsed = Self::parse(&rule.replace('_', " "));
+                if parsed.is_none() {
+                    println!("Unknown redaction rule {rule}");
+                }
+                parsed
+            })
+            .collect()
+    }
+
+    fn parse(rule: &str) -> Option<Self> {
+        let (column, test) = match rule.find(['>', '<', '=']) {
+            Some(index) => {
+                let (column, test) = rule.split_at(index);
+                let value = &test[1..];
+                let test = match test.as_bytes()[0] {
+                    b'>' => CellTest::Over(cell_amount(value)?),
+                    b'<' => CellTest::Under(cell_amount(value)?),
+                    _ => CellTest::Equals(value.trim().to_lowercase()),
+                };
+                (column, test)
+            }
+            None => (rule, CellTest::Any),
+        };
+        let column = match column.trim() {
+            "" => return None,
+            "*" => None,
+            column => Some(column.to_lowercase()),
+        };
+        Some(Self { column, test })
+    }
+
+    // whether a cell under `header` should be redacted
+    pub fn matches(&self, header: &str, cell: &str) -> bool {
+        let header = header.trim().trim_end_matches(':').to_lowercase();
+        if self.column.as_ref().is_some_and(|column| *column != header) {
+            return false;
+        }
+        match &self.test {
+            CellTest::Any => !cell.trim().is_empty(),
+            CellTest::Over(limit) => cell_amount(cell).is_some_and(|amount| amount > *limit),
+            CellTest::Under(limit) => cell_amount(cell).is_some_and(|amount| amount < *limit),
+            CellTest::Equals(value) => cell.trim().to_lowercase() == *value,
+        }
+    }
+}
+
+// the first number in a cell, ignoring currency signs and thousands separators
+pub fn cell_amount(cell: &str) -> Option<f32> {
+    let cell = cell.replace(',', "");
+    let start = cell.find(|c: char| c.is_ascii_digit())?;
+    let negative = cell[..start].ends_with('-');
+    let number: String = cell[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let amount: f32 = number.trim_end_matches('.').parse().ok()?;
+    Some(if negative { -amount } else { amount })
+}
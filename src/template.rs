@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+
+use crate::layout::{LINE_HEIGHT, LINE_WIDTH};
+
+pub const DEFAULT_PHOTO: &str = "textures/mob/mob1.png";
+
+// Kind of document, picked with a leading tag like `@email`
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Template {
+    // typed memo, the plain page every document used to be
+    #[default]
+    Memo,
+    // printout with faded header lines above the first rule
+    Email,
+    // handwritten in pen on a notepad
+    Note,
+    // rows split with `//` and cells with `|`, the first row heads the columns
+    Sheet,
+    // print at the top of the page with a caption below, a leading image block fills it
+    Photo,
+    // one field per `//`, labels above underlined values
+    Form,
+}
+
+// Colour the text of a line is drawn in
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Ink {
+    Typed,
+    Faded,
+    Pen,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct LineStyle {
+    pub scale: f32,
+    pub ink: Ink,
+}
+
+impl LineStyle {
+    const TYPED: LineStyle = LineStyle {
+        scale: 1.0,
+        ink: Ink::Typed,
+    };
+    const SMALL: LineStyle = LineStyle {
+        scale: 0.85,
+        ink: Ink::Typed,
+    };
+    const LABEL: LineStyle = LineStyle {
+        scale: 0.75,
+        ink: Ink::Faded,
+    };
+    const HEADER: LineStyle = LineStyle {
+        scale: 0.85,
+        ink: Ink::Faded,
+    };
+    const HANDWRITTEN: LineStyle = LineStyle {
+        scale: 1.1,
+        ink: Ink::Pen,
+    };
+}
+
+impl Template {
+    // splits the tag off the front of a document, `@photo:<image path>` names the print and
+    // `@sheet:<rules>` or `@form:<rules>` lists what to redact by column or label
+    pub fn parse(text: &str) -> (Self, Option<&str>, &str) {
+        let text = text.trim_start();
+        let Some(tagged) = text.strip_prefix('@') else {
+            return (Template::Memo, None, text);
+        };
+        let (tag, body) = tagged.split_once(' ').unwrap_or((tagged, ""));
+        let (name, argument) = match tag.split_once(':') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (tag, None),
+        };
+        let template = match name {
+            "memo" => Template::Memo,
+            "email" => Template::Email,
+            "note" => Template::Note,
+            "sheet" => Template::Sheet,
+            "photo" => Template::Photo,
+            "form" => Template::Form,
+            _ => {
+                println!("Unknown document template @{name}");
+                return (Template::Memo, None, text);
+            }
+        };
+        (template, argument, body)
+    }
+
+    pub fn paper_color(&self) -> Color {
+        match self {
+            Template::Memo | Template::Photo => Color::WHITE,
+            Template::Email => Color::srgb(0.96, 0.96, 0.95),
+            Template::Note => Color::srgb(1.0, 0.95, 0.68),
+            Template::Sheet => Color::srgb(0.91, 0.97, 0.91),
+            Template::Form => Color::srgb(0.92, 0.94, 1.0),
+        }
+    }
+
+    // style of the lines before the first `---` rule, and of a sheet's column headers
+    pub fn header_style(&self) -> LineStyle {
+        match self {
+            Template::Email | Template::Sheet => LineStyle::HEADER,
+            _ => self.body_style(),
+        }
+    }
+
+    pub fn body_style(&self) -> LineStyle {
+        match self {
+            Template::Note => LineStyle::HANDWRITTEN,
+            Template::Sheet | Template::Photo => LineStyle::SMALL,
+            _ => LineStyle::TYPED,
+        }
+    }
+
+    pub fn label_style(&self) -> LineStyle {
+        LineStyle::LABEL
+    }
+
+    // left edge of the text and how wide the lines can be
+    pub fn margins(&self) -> (f32, f32) {
+        match self {
+            Template::Note => (0.05, LINE_WIDTH - 0.08),
+            Template::Form => (0.02, LINE_WIDTH - 0.02),
+            _ => (0.0, LINE_WIDTH),
+        }
+    }
+
+    pub fn line_spacing(&self) -> f32 {
+        match self {
+            Template::Note => 1.3,
+            Template::Sheet => 1.2,
+            _ => 1.0,
+        }
+    }
+
+    // handwriting drifts a little off level from line to line
+    pub fn tilt(&self, line_index: usize) -> f32 {
+        match self {
+            Template::Note => (line_index as f32 * 2.3).sin() * 0.025,
+            _ => 0.0,
+        }
+    }
+
+    // the print on a photo page, above the caption
+    pub fn photo_rect(&self) -> Option<Rect> {
+        match self {
+            Template::Photo => Some(Rect::new(
+                0.04,
+                -LINE_HEIGHT * 0.5,
+                LINE_WIDTH - 0.04,
+                14.0 * LINE_HEIGHT,
+            )),
+            _ => None,
+        }
+    }
+}
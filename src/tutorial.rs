@@ -0,0 +1,255 @@
+use bevy::prelude::*;
+
+use crate::{
+    ActiveShift, GameMode, GameState,
+    audio::DuckEvent,
+    camera::{CameraView, Viewpoint},
+    cap::{CapEvent, CapState},
+    feedback::{FeedbackEvent, Feedbacks},
+    locale::{Locale, UI_FONT_PATH},
+    paper::{HoldPage, Page, PageContent, page_position},
+    stack::PageFiledEvent,
+};
+
+const TUTORIAL_DOCUMENT: &str = "assets/text/tutorial.txt";
+// long enough that nobody runs out of time while learning
+const TUTORIAL_LIFETIME: f32 = 600.0;
+// how long the boss talks over the mob with each new instruction
+const PROMPT_SECONDS: f32 = 2.0;
+const PROMPT_FONT_SIZE: f32 = 28.0;
+const PROMPT_TEXT_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+const PROMPT_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.92, 0.3);
+
+// What the boss is waiting for the player to do, in the order they are taught
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+enum TutorialStep {
+    LookAtPage,
+    Uncap,
+    Redact,
+    Refill,
+    TurnPage,
+    // nothing left to wait for, the practice page is the player's own
+    Done,
+}
+
+impl TutorialStep {
+    fn next(&self) -> Self {
+        match self {
+            TutorialStep::LookAtPage => TutorialStep::Uncap,
+            TutorialStep::Uncap => TutorialStep::Redact,
+            TutorialStep::Redact => TutorialStep::Refill,
+            TutorialStep::Refill => TutorialStep::TurnPage,
+            TutorialStep::TurnPage | TutorialStep::Done => TutorialStep::Done,
+        }
+    }
+
+    fn prompt_key(&self) -> &'static str {
+        match self {
+            TutorialStep::LookAtPage => "tutorial-look",
+            TutorialStep::Uncap => "tutorial-uncap",
+            TutorialStep::Redact => "tutorial-redact",
+            TutorialStep::Refill => "tutorial-refill",
+            TutorialStep::TurnPage => "tutorial-turn",
+            TutorialStep::Done => "tutorial-done",
+        }
+    }
+}
+
+// Only present while the tutorial is being played
+#[derive(Resource)]
+struct Tutorial {
+    step: TutorialStep,
+}
+
+#[derive(Component)]
+struct TutorialPrompt;
+
+// Marks the words the boss wants blacked out on the first sheet
+#[derive(Component)]
+struct Highlight;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::PLAYING), setup)
+        .add_systems(OnExit(GameState::PLAYING), cleanup)
+        .add_systems(
+            Update,
+            (watch_view, show_prompt, highlight_targets)
+                .chain()
+                .run_if(in_state(GameState::PLAYING).and(resource_exists::<Tutorial>)),
+        )
+        .add_observer(watch_cap)
+        .add_observer(watch_redaction)
+        .add_observer(watch_pages);
+}
+
+// the practice shift started from the main menu
+pub fn active_shift(locale: &Locale) -> ActiveShift {
+    ActiveShift {
+        mode: GameMode::Tutorial,
+        document: TUTORIAL_DOCUMENT.to_owned(),
+        directive: locale.get("tutorial-directive"),
+        lifetime: TUTORIAL_LIFETIME,
+        unlocks: Vec::new(),
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_shift: Res<ActiveShift>,
+    mut hold_page: ResMut<HoldPage>,
+) {
+    let tutorial = active_shift.mode == GameMode::Tutorial;
+    // the first sheet stays on the desk until the boss asks for it to be filed
+    hold_page.0 = tutorial;
+    if !tutorial {
+        return;
+    }
+    commands.insert_resource(Tutorial {
+        step: TutorialStep::LookAtPage,
+    });
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: px(40),
+                width: percent(100),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            DespawnOnExit(GameState::PLAYING),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    max_width: percent(70),
+                    padding: UiRect::axes(px(16), px(10)),
+                    ..default()
+                },
+                BackgroundColor(PROMPT_BACKGROUND),
+                children![(
+                    Text::default(),
+                    TextFont {
+                        font: asset_server.load(UI_FONT_PATH),
+                        font_size: PROMPT_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(PROMPT_TEXT_COLOR),
+                    TutorialPrompt,
+                )],
+            ));
+        });
+}
+
+fn cleanup(mut commands: Commands, mut hold_page: ResMut<HoldPage>) {
+    commands.remove_resource::<Tutorial>();
+    hold_page.0 = false;
+}
+
+// moves on only from the step the action belongs to, so nothing is skipped by acting early
+fn advance(tutorial: &mut ResMut<Tutorial>, from: TutorialStep) {
+    if tutorial.step == from {
+        tutorial.step = from.next();
+    }
+}
+
+fn watch_view(view: Res<CameraView>, mut tutorial: ResMut<Tutorial>) {
+    if view.viewpoint == Viewpoint::Page {
+        advance(&mut tutorial, TutorialStep::LookAtPage);
+    }
+}
+
+fn watch_cap(event: On<CapEvent>, tutorial: Option<ResMut<Tutorial>>) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    if event.from == CapState::Capped {
+        advance(&mut tutorial, TutorialStep::Uncap);
+    }
+    if event.to == CapState::Refilling {
+        advance(&mut tutorial, TutorialStep::Refill);
+    }
+}
+
+fn watch_redaction(event: On<FeedbackEvent>, tutorial: Option<ResMut<Tutorial>>) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    if event.feedback == Feedbacks::Correct {
+        advance(&mut tutorial, TutorialStep::Redact);
+    }
+}
+
+fn watch_pages(_filed: On<PageFiledEvent>, tutorial: Option<ResMut<Tutorial>>) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    advance(&mut tutorial, TutorialStep::TurnPage);
+}
+
+// the boss gives each instruction over the noise of the mob
+fn show_prompt(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    locale: Res<Locale>,
+    mut hold_page: ResMut<HoldPage>,
+    mut prompt: Single<&mut Text, With<TutorialPrompt>>,
+) {
+    if !tutorial.is_changed() {
+        return;
+    }
+    prompt.0 = format!(
+        "{} {}",
+        locale.get("planner-boss"),
+        locale.get(tutorial.step.prompt_key())
+    );
+    hold_page.0 = tutorial.step < TutorialStep::TurnPage;
+    commands.trigger(DuckEvent {
+        seconds: PROMPT_SECONDS,
+    });
+}
+
+// lays a highlighter stripe under each letter to redact on the first sheet, turning the page
+// clears it with the rest of the page's content
+fn highlight_targets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    page: Single<&Page>,
+    highlights: Query<(), With<Highlight>>,
+) {
+    if page.page_num != 0 || !highlights.is_empty() {
+        return;
+    }
+    let Some(layout) = page.pages.pages.first() else {
+        return;
+    };
+    let targets: Vec<_> = layout
+        .glyphs
+        .iter()
+        .filter(|glyph| glyph.to_redact)
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+    let mesh = meshes.add(Plane3d::default().mesh().size(1.0, 1.0));
+    let material = materials.add(StandardMaterial {
+        base_color: HIGHLIGHT_COLOR,
+        unlit: true,
+        ..default()
+    });
+    for glyph in targets {
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            // between the rules and the photos, under the text
+            Transform::from_translation(page_position(glyph.centre(), glyph.y) - Vec3::Y * 0.0003)
+                .with_scale(Vec3::new(glyph.advance, 1.0, glyph.height)),
+            Highlight,
+            PageContent,
+            DespawnOnExit(GameState::PLAYING),
+        ));
+    }
+}